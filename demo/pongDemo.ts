@@ -29,17 +29,13 @@ function tick(frame: bigint, now: number, inputs: ArrayBuffer) {
 
 		let bytes: Uint8Array;
 		try {
-			bytes = new Uint8Array(memory.buffer, ptr, 10);
+			bytes = new Uint8Array(memory.buffer, ptr, rustPong.input_buffer_length());
 		} catch(e) {
 			console.error(e);
 			window.location.reload();
 		}
 
-		let i = 0;
-		var byteArray = new Uint8Array(inputs);
-		for(var byte of byteArray) {
-			bytes[i++] = byte;
-		}
+		bytes.set(new Uint8Array(inputs, 0, Math.min(inputs.byteLength, bytes.length)));
 		rustPong.tick(frame);
 		render(rustPong.state);
 	}
@@ -1,6 +1,6 @@
 mod pong_config;
-use pogp::inputs::{KeyboardInput, KeyboardSnapshot};
-use pong::GameState;
+use pogp::inputs::{ActionMap, InputFrame, KeyboardInput, KeyboardSnapshot, MAX_FRAME_LENGTH};
+use pong::{Action, GameState};
 use pong_config::PongConfig;
 
 pub mod pong;
@@ -56,8 +56,9 @@ cfg_if::cfg_if! {
 
 #[cfg_attr(target_family = "wasm", wasm_bindgen)]
 pub struct Game {
-    input_buffer: [u8; 10],
+    input_buffer: [u8; MAX_FRAME_LENGTH],
     keyboard: KeyboardSnapshot,
+    actions: ActionMap<Action>,
     pub state: GameState,
     config: PongConfig,
 
@@ -74,8 +75,9 @@ impl Game {
         utils::set_panic_hook();
 
         let mut game = Self {
-            input_buffer: [0; 10],
+            input_buffer: [0; MAX_FRAME_LENGTH],
             keyboard: KeyboardSnapshot::new(),
+            actions: pong::default_actions(),
             state: Default::default(),
             config: Default::default(),
             accumulator: 0,
@@ -88,15 +90,13 @@ impl Game {
 
     pub fn tick(&mut self, _frame: u64) {
         // apply inputs from binary pogp input buffer to move paddles
-        let keyboard_input = KeyboardInput::from(self.input_buffer);
-        self.apply_inputs(keyboard_input);
+        self.apply_inputs(read_keyboard(&self.input_buffer));
 
         self.shared_tick(_frame);
     }
 
     pub fn unity_tick(&mut self, input_buffer: &[u8], _frame: u64) {
-        let keyboard_input = KeyboardInput::from(input_buffer);
-        self.apply_inputs(keyboard_input);
+        self.apply_inputs(read_keyboard(input_buffer));
 
         self.shared_tick(_frame);
     }
@@ -126,10 +126,22 @@ impl Game {
     fn apply_inputs(&mut self, keyboard_input: KeyboardInput) {
         self.keyboard.add_input(keyboard_input);
 
-        pong::process_inputs(&mut self.state, &self.config, &self.keyboard);
+        pong::process_inputs(&mut self.state, &self.config, &self.actions, &self.keyboard);
     }
 
     pub fn input_buffer(&self) -> *const u8 {
         return self.input_buffer.as_ptr();
     }
+
+    pub fn input_buffer_length(&self) -> usize {
+        self.input_buffer.len()
+    }
+}
+
+/// the keyboard region of a pogp input buffer, a malformed keyboard counts as no keys pressed
+/// rather than crashing the host, and other devices can't affect it
+fn read_keyboard(buffer: &[u8]) -> KeyboardInput {
+    InputFrame::read_keyboard(buffer)
+        .and_then(Result::ok)
+        .unwrap_or_default()
 }
@@ -1,4 +1,4 @@
-use pogp::inputs::{Key, KeyboardSnapshot};
+use pogp::inputs::{ActionMap, Binding, Key, KeyboardSnapshot};
 #[cfg(target_family = "wasm")]
 use wasm_bindgen::prelude::*;
 
@@ -8,17 +8,39 @@ pub fn start(state: &mut GameState, config: &PongConfig) {
     reset(state, config);
 }
 
-pub fn process_inputs(state: &mut GameState, config: &PongConfig, keyboard: &KeyboardSnapshot) {
-    if keyboard.is_key(Key::ArrowDown) {
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Action {
+    P0Up,
+    P0Down,
+    P1Up,
+    P1Down,
+}
+
+pub fn default_actions() -> ActionMap<Action> {
+    let mut actions = ActionMap::new();
+    actions.bind(Action::P0Up, Binding::Key(Key::ArrowUp));
+    actions.bind(Action::P0Down, Binding::Key(Key::ArrowDown));
+    actions.bind(Action::P1Up, Binding::Key(Key::KeyW));
+    actions.bind(Action::P1Down, Binding::Key(Key::KeyS));
+    actions
+}
+
+pub fn process_inputs(
+    state: &mut GameState,
+    config: &PongConfig,
+    actions: &ActionMap<Action>,
+    keyboard: &KeyboardSnapshot,
+) {
+    if actions.action_held(Action::P0Down, keyboard) {
         state.p0.y += config.paddle_speed;
     }
-    if keyboard.is_key(Key::ArrowUp) {
+    if actions.action_held(Action::P0Up, keyboard) {
         state.p0.y -= config.paddle_speed;
     }
-    if keyboard.is_key(Key::KeyW) {
+    if actions.action_held(Action::P1Up, keyboard) {
         state.p1.y -= config.paddle_speed;
     }
-    if keyboard.is_key(Key::KeyS) {
+    if actions.action_held(Action::P1Down, keyboard) {
         state.p1.y += config.paddle_speed;
     }
 }
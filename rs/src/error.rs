@@ -0,0 +1,86 @@
+use std::fmt;
+
+use crate::inputs::InputType;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum PogpError {
+    /// the type byte at the start of an input did not match the input being decoded
+    WrongInputType {
+        expected: InputType,
+        actual: u8,
+    },
+    /// the buffer ended before the input did
+    Truncated {
+        expected: usize,
+        actual: usize,
+    },
+    /// a list was longer than the binary format allows, e.g. fingers of a touch input
+    TooMany {
+        what: &'static str,
+        max: usize,
+        actual: usize,
+    },
+    /// a string was not a w3c UIEvents key code
+    UnknownKeyCode(String),
+    /// a string was not a chord like "Ctrl+Shift+S"
+    InvalidChord(String),
+    UnknownButtonPosition(u8),
+    /// a string was not a button position name like "RightFaceBottom"
+    UnknownButtonName(String),
+    UnknownHand(u8),
+    /// a string in the buffer was not valid utf-8
+    InvalidString,
+    /// a line of a binding profile couldn't be loaded, lines start at 1
+    InvalidProfile {
+        line: usize,
+        error: Box<PogpError>,
+    },
+    /// a binding profile line wasn't a `[section]` or a `name = value` the profile expects
+    ProfileSyntax(String),
+    /// the binding profile was written by a newer version of pogp
+    UnsupportedProfileVersion(u32),
+}
+
+impl fmt::Display for PogpError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PogpError::WrongInputType { expected, actual } => write!(
+                f,
+                "Expected first byte to be {}, but it was {}",
+                *expected as u8, actual
+            ),
+            PogpError::Truncated { expected, actual } => write!(
+                f,
+                "Expected buffer of at least {} bytes, but it was {}",
+                expected, actual
+            ),
+            PogpError::TooMany { what, max, actual } => write!(
+                f,
+                "Expected at most {} {}, but there were {}",
+                max, what, actual
+            ),
+            PogpError::UnknownKeyCode(code) => write!(f, "Unknown key code {:?}", code),
+            PogpError::InvalidChord(chord) => write!(f, "Invalid key chord {:?}", chord),
+            PogpError::UnknownButtonPosition(position) => {
+                write!(f, "Unknown button position {}", position)
+            }
+            PogpError::UnknownButtonName(name) => {
+                write!(f, "Unknown button position name {:?}", name)
+            }
+            PogpError::UnknownHand(hand) => write!(f, "Unknown hand {}", hand),
+            PogpError::InvalidString => write!(f, "Expected string to be valid utf-8"),
+            PogpError::InvalidProfile { line, error } => {
+                write!(f, "Invalid binding profile on line {}: {}", line, error)
+            }
+            PogpError::ProfileSyntax(reason) => write!(f, "Expected {}", reason),
+            PogpError::UnsupportedProfileVersion(version) => write!(
+                f,
+                "Unsupported binding profile version {}, expected at most {}",
+                version,
+                crate::inputs::BINDING_PROFILE_VERSION
+            ),
+        }
+    }
+}
+
+impl std::error::Error for PogpError {}
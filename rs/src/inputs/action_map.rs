@@ -0,0 +1,307 @@
+use super::{ButtonPosition, GamepadSnapshot, Hand, Key, KeyboardSnapshot, MouseSnapshot};
+
+/// Binding is a physical input an action can be bound to.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Binding {
+    Key(Key),
+    Gamepad(ButtonPosition),
+    /// the mouse button, pogp mouse input has one
+    Mouse,
+}
+
+/// BindingSource reports whether bindings are held, it's implemented by the snapshots and by
+/// tuples of them to check several devices at once.
+pub trait BindingSource {
+    /// whether `binding` was held `frames_ago` frames before the latest input, only 0 and 1 are
+    /// needed. False for bindings of other devices.
+    fn is_held(&self, binding: Binding, frames_ago: usize) -> bool;
+
+    /// x and y of the stick for `hand` on the latest input, None for sources without sticks
+    fn stick(&self, _hand: Hand) -> Option<(i64, i64)> {
+        None
+    }
+
+    /// appends every binding held `frames_ago` frames before the latest input to `bindings`,
+    /// used to capture new bindings
+    fn held_bindings(&self, _frames_ago: usize, _bindings: &mut Vec<Binding>) {}
+}
+
+impl<const N: usize> BindingSource for KeyboardSnapshot<N> {
+    fn is_held(&self, binding: Binding, frames_ago: usize) -> bool {
+        match binding {
+            Binding::Key(key) => self.was_key_down(key, frames_ago),
+            _ => false,
+        }
+    }
+
+    fn held_bindings(&self, frames_ago: usize, bindings: &mut Vec<Binding>) {
+        if let Some(input) = self.frames().nth(frames_ago) {
+            bindings.extend(input.keys().map(Binding::Key));
+        }
+    }
+}
+
+impl<const N: usize> BindingSource for GamepadSnapshot<N> {
+    fn is_held(&self, binding: Binding, frames_ago: usize) -> bool {
+        match binding {
+            Binding::Gamepad(position) => self.is_button(position, frames_ago),
+            _ => false,
+        }
+    }
+
+    fn stick(&self, hand: Hand) -> Option<(i64, i64)> {
+        self.axes(hand).map(|axes| (axes.x, axes.y))
+    }
+
+    fn held_bindings(&self, frames_ago: usize, bindings: &mut Vec<Binding>) {
+        bindings.extend(
+            ButtonPosition::ALL
+                .into_iter()
+                .filter(|position| self.is_button(*position, frames_ago))
+                .map(Binding::Gamepad),
+        );
+    }
+}
+
+impl BindingSource for MouseSnapshot {
+    fn is_held(&self, binding: Binding, frames_ago: usize) -> bool {
+        match (binding, frames_ago) {
+            (Binding::Mouse, 0) => self.is_clicked(),
+            // held last frame unless it went down this frame, or it came up this frame
+            (Binding::Mouse, 1) => {
+                (self.is_clicked() && !self.is_click_down()) || self.is_click_up()
+            }
+            _ => false,
+        }
+    }
+
+    fn held_bindings(&self, frames_ago: usize, bindings: &mut Vec<Binding>) {
+        if self.is_held(Binding::Mouse, frames_ago) {
+            bindings.push(Binding::Mouse);
+        }
+    }
+}
+
+impl<T: BindingSource + ?Sized> BindingSource for &T {
+    fn is_held(&self, binding: Binding, frames_ago: usize) -> bool {
+        (**self).is_held(binding, frames_ago)
+    }
+
+    fn stick(&self, hand: Hand) -> Option<(i64, i64)> {
+        (**self).stick(hand)
+    }
+
+    fn held_bindings(&self, frames_ago: usize, bindings: &mut Vec<Binding>) {
+        (**self).held_bindings(frames_ago, bindings)
+    }
+}
+
+impl<A: BindingSource, B: BindingSource> BindingSource for (A, B) {
+    fn is_held(&self, binding: Binding, frames_ago: usize) -> bool {
+        self.0.is_held(binding, frames_ago) || self.1.is_held(binding, frames_ago)
+    }
+
+    fn stick(&self, hand: Hand) -> Option<(i64, i64)> {
+        self.0.stick(hand).or_else(|| self.1.stick(hand))
+    }
+
+    fn held_bindings(&self, frames_ago: usize, bindings: &mut Vec<Binding>) {
+        self.0.held_bindings(frames_ago, bindings);
+        self.1.held_bindings(frames_ago, bindings);
+    }
+}
+
+impl<A: BindingSource, B: BindingSource, C: BindingSource> BindingSource for (A, B, C) {
+    fn is_held(&self, binding: Binding, frames_ago: usize) -> bool {
+        self.0.is_held(binding, frames_ago)
+            || self.1.is_held(binding, frames_ago)
+            || self.2.is_held(binding, frames_ago)
+    }
+
+    fn stick(&self, hand: Hand) -> Option<(i64, i64)> {
+        self.0
+            .stick(hand)
+            .or_else(|| self.1.stick(hand))
+            .or_else(|| self.2.stick(hand))
+    }
+
+    fn held_bindings(&self, frames_ago: usize, bindings: &mut Vec<Binding>) {
+        self.0.held_bindings(frames_ago, bindings);
+        self.1.held_bindings(frames_ago, bindings);
+        self.2.held_bindings(frames_ago, bindings);
+    }
+}
+
+/// ActionMap binds game actions, usually an enum, to any number of physical inputs so game logic
+/// doesn't depend on keys or buttons.
+///
+/// An action is held while any of its bindings are held, so pressing a second binding while the
+/// first is held doesn't make the action go down again.
+#[derive(Clone, Debug)]
+pub struct ActionMap<A> {
+    actions: Vec<(A, Vec<Binding>)>,
+}
+
+impl<A: Copy + PartialEq> ActionMap<A> {
+    pub fn new() -> Self {
+        Self {
+            actions: Vec::new(),
+        }
+    }
+
+    /// adds `binding` to `action`, bindings can be shared between actions
+    pub fn bind(&mut self, action: A, binding: Binding) {
+        match self.actions.iter_mut().find(|(a, _)| *a == action) {
+            Some((_, bindings)) if bindings.contains(&binding) => {}
+            Some((_, bindings)) => bindings.push(binding),
+            None => self.actions.push((action, vec![binding])),
+        }
+    }
+
+    pub fn unbind(&mut self, action: A, binding: Binding) {
+        if let Some((_, bindings)) = self.actions.iter_mut().find(|(a, _)| *a == action) {
+            bindings.retain(|b| *b != binding);
+        }
+    }
+
+    /// removes every binding of `action` but keeps the action, so `actions` still lists it and a
+    /// saved profile writes it as unbound instead of leaving it to its defaults. Before binding
+    /// profiles, clear removed the action entirely.
+    pub fn clear(&mut self, action: A) {
+        if let Some((_, bindings)) = self.actions.iter_mut().find(|(a, _)| *a == action) {
+            bindings.clear();
+        }
+    }
+
+    /// every action that's been bound, in the order they were first bound
+    pub fn actions(&self) -> impl Iterator<Item = A> + '_ {
+        self.actions.iter().map(|(action, _)| *action)
+    }
+
+    pub fn bindings(&self, action: A) -> &[Binding] {
+        self.actions
+            .iter()
+            .find(|(a, _)| *a == action)
+            .map_or(&[], |(_, bindings)| bindings.as_slice())
+    }
+
+    /// true for every frame any binding of `action` is held
+    pub fn action_held(&self, action: A, source: &impl BindingSource) -> bool {
+        self.is_held(action, source, 0)
+    }
+
+    /// true only for the frame `action` starts being held
+    pub fn action_down(&self, action: A, source: &impl BindingSource) -> bool {
+        self.is_held(action, source, 0) && !self.is_held(action, source, 1)
+    }
+
+    /// true only for the frame `action` stops being held
+    pub fn action_up(&self, action: A, source: &impl BindingSource) -> bool {
+        !self.is_held(action, source, 0) && self.is_held(action, source, 1)
+    }
+
+    fn is_held(&self, action: A, source: &impl BindingSource, frames_ago: usize) -> bool {
+        self.bindings(action)
+            .iter()
+            .any(|binding| source.is_held(*binding, frames_ago))
+    }
+}
+
+impl<A: Copy + PartialEq> Default for ActionMap<A> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod action_map {
+    use super::*;
+    use crate::inputs::{GamepadInput, KeyboardInput, MouseInput};
+
+    #[derive(Clone, Copy, Debug, PartialEq, Eq)]
+    enum Action {
+        Jump,
+        Fire,
+    }
+
+    fn action_map() -> ActionMap<Action> {
+        let mut actions = ActionMap::new();
+        actions.bind(Action::Jump, Binding::Key(Key::Space));
+        actions.bind(Action::Jump, Binding::Key(Key::KeyW));
+        actions.bind(
+            Action::Jump,
+            Binding::Gamepad(ButtonPosition::RightFaceBottom),
+        );
+        actions.bind(Action::Fire, Binding::Mouse);
+        actions
+    }
+
+    #[test]
+    fn bind() {
+        let mut actions = action_map();
+        actions.bind(Action::Jump, Binding::Key(Key::Space));
+        assert_eq!(actions.bindings(Action::Jump).len(), 3, "binds only once");
+
+        actions.unbind(Action::Jump, Binding::Key(Key::KeyW));
+        assert_eq!(actions.bindings(Action::Jump).len(), 2);
+
+        actions.clear(Action::Jump);
+        assert_eq!(actions.bindings(Action::Jump), &[]);
+        assert_eq!(
+            actions.actions().collect::<Vec<_>>(),
+            vec![Action::Jump, Action::Fire],
+            "cleared actions are still listed"
+        );
+    }
+
+    #[test]
+    fn keyboard() {
+        let actions = action_map();
+        let mut keyboard = KeyboardSnapshot::new();
+        assert_eq!(actions.action_held(Action::Jump, &keyboard), false);
+
+        keyboard.add_input(KeyboardInput::from(vec![Key::Space]));
+        assert_eq!(actions.action_down(Action::Jump, &keyboard), true);
+        assert_eq!(actions.action_held(Action::Jump, &keyboard), true);
+
+        keyboard.add_input(KeyboardInput::from(vec![Key::Space, Key::KeyW]));
+        assert_eq!(
+            actions.action_down(Action::Jump, &keyboard),
+            false,
+            "false on second binding while held"
+        );
+
+        keyboard.add_input(KeyboardInput::from(vec![Key::KeyW]));
+        assert_eq!(actions.action_up(Action::Jump, &keyboard), false);
+
+        keyboard.add_input(Default::default());
+        assert_eq!(actions.action_up(Action::Jump, &keyboard), true);
+    }
+
+    #[test]
+    fn devices() {
+        let actions = action_map();
+        let keyboard = KeyboardSnapshot::new();
+        let mut gamepad = GamepadSnapshot::new();
+        let mut mouse = MouseSnapshot::new();
+
+        let mut input = GamepadInput::new();
+        input.set_button(ButtonPosition::RightFaceBottom, 10000);
+        gamepad.add_input(input);
+        mouse.add_input(MouseInput::new(0, 0, true));
+
+        let source = (&keyboard, &gamepad, &mouse);
+        assert_eq!(actions.action_down(Action::Jump, &source), true);
+        assert_eq!(actions.action_down(Action::Fire, &source), true);
+        assert_eq!(
+            actions.action_held(Action::Fire, &keyboard),
+            false,
+            "false for other devices"
+        );
+
+        mouse.add_input(MouseInput::new(0, 0, true));
+        assert_eq!(actions.action_down(Action::Fire, &mouse), false);
+        mouse.add_input(MouseInput::new(0, 0, false));
+        assert_eq!(actions.action_up(Action::Fire, &mouse), true);
+    }
+}
@@ -0,0 +1,269 @@
+use super::{Binding, BindingSource, Hand, Key, DEFAULT_DEAD_ZONE, STICK_MAX};
+
+/// Vector2 is a direction in screen coordinates, so y is positive down like gamepad sticks.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Vector2 {
+    pub x: f32,
+    pub y: f32,
+}
+
+impl Vector2 {
+    pub const ZERO: Vector2 = Vector2 { x: 0.0, y: 0.0 };
+
+    pub fn new(x: f32, y: f32) -> Self {
+        Self { x, y }
+    }
+
+    pub fn length(&self) -> f32 {
+        (self.x * self.x + self.y * self.y).sqrt()
+    }
+
+    /// the same direction with a length of at most 1
+    pub fn clamp_length(&self) -> Vector2 {
+        let length = self.length();
+        if length <= 1.0 {
+            return *self;
+        }
+        Vector2::new(self.x / length, self.y / length)
+    }
+}
+
+/// what an axis reads when both of its opposite bindings are held
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum OppositePolicy {
+    /// the axis is 0
+    #[default]
+    Cancel,
+    /// the binding pressed most recently wins, e.g. holding left then pressing right goes right
+    LastPressedWins,
+}
+
+/// Axis1d reads -1, 0 or 1 from a pair of opposite bindings, e.g. A and D.
+///
+/// It remembers which binding was pressed last for `OppositePolicy::LastPressedWins`, so call
+/// `update` once per frame.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Axis1d {
+    pub negative: Binding,
+    pub positive: Binding,
+    pub policy: OppositePolicy,
+    /// sign of the binding pressed last
+    last_pressed: i8,
+}
+
+impl Axis1d {
+    pub fn new(negative: Binding, positive: Binding) -> Self {
+        Self {
+            negative,
+            positive,
+            policy: OppositePolicy::default(),
+            last_pressed: 0,
+        }
+    }
+
+    pub fn with_policy(self, policy: OppositePolicy) -> Self {
+        Self { policy, ..self }
+    }
+
+    pub fn update(&mut self, source: &impl BindingSource) -> f32 {
+        let held = |binding| source.is_held(binding, 0);
+        let pressed = |binding| source.is_held(binding, 0) && !source.is_held(binding, 1);
+        if pressed(self.negative) {
+            self.last_pressed = -1;
+        }
+        if pressed(self.positive) {
+            self.last_pressed = 1;
+        }
+
+        match (held(self.negative), held(self.positive)) {
+            (true, false) => -1.0,
+            (false, true) => 1.0,
+            (true, true) => match self.policy {
+                OppositePolicy::Cancel => 0.0,
+                OppositePolicy::LastPressedWins => self.last_pressed as f32,
+            },
+            (false, false) => 0.0,
+        }
+    }
+}
+
+/// Axis2d reads a direction from four bindings, a gamepad stick, or both.
+///
+/// Diagonals from the bindings are normalized so moving diagonally isn't faster. When the
+/// bindings are released the stick is read instead, with values inside the dead zone read as 0.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Axis2d {
+    pub x: Axis1d,
+    pub y: Axis1d,
+    /// the stick to read, if any
+    pub stick: Option<Hand>,
+    /// distance from the center of the stick that reads as 0, 10000 is the full range
+    pub dead_zone: i64,
+}
+
+impl Axis2d {
+    pub fn new(up: Binding, down: Binding, left: Binding, right: Binding) -> Self {
+        Self {
+            x: Axis1d::new(left, right),
+            y: Axis1d::new(up, down),
+            stick: None,
+            dead_zone: DEFAULT_DEAD_ZONE,
+        }
+    }
+
+    pub fn wasd() -> Self {
+        Self::from_keys(Key::KeyW, Key::KeyS, Key::KeyA, Key::KeyD)
+    }
+
+    pub fn arrows() -> Self {
+        Self::from_keys(
+            Key::ArrowUp,
+            Key::ArrowDown,
+            Key::ArrowLeft,
+            Key::ArrowRight,
+        )
+    }
+
+    pub fn from_keys(up: Key, down: Key, left: Key, right: Key) -> Self {
+        Self::new(
+            Binding::Key(up),
+            Binding::Key(down),
+            Binding::Key(left),
+            Binding::Key(right),
+        )
+    }
+
+    pub fn with_stick(self, hand: Hand) -> Self {
+        Self {
+            stick: Some(hand),
+            ..self
+        }
+    }
+
+    pub fn with_policy(self, policy: OppositePolicy) -> Self {
+        Self {
+            x: self.x.with_policy(policy),
+            y: self.y.with_policy(policy),
+            ..self
+        }
+    }
+
+    /// direction this frame with a length of at most 1
+    pub fn update(&mut self, source: &impl BindingSource) -> Vector2 {
+        let digital = Vector2::new(self.x.update(source), self.y.update(source));
+        if digital != Vector2::ZERO {
+            return digital.clamp_length();
+        }
+
+        let Some((x, y)) = self.stick.and_then(|hand| source.stick(hand)) else {
+            return Vector2::ZERO;
+        };
+        let max = STICK_MAX as f32;
+        let stick = Vector2::new(x as f32 / max, y as f32 / max);
+        if stick.length() * max <= self.dead_zone as f32 {
+            return Vector2::ZERO;
+        }
+        stick.clamp_length()
+    }
+}
+
+#[cfg(test)]
+mod axis {
+    use super::*;
+    use crate::inputs::{GamepadInput, GamepadSnapshot, KeyboardInput, KeyboardSnapshot};
+
+    #[test]
+    fn axis1d() {
+        let mut axis = Axis1d::new(Binding::Key(Key::KeyA), Binding::Key(Key::KeyD));
+        let mut keyboard = KeyboardSnapshot::new();
+        assert_eq!(axis.update(&keyboard), 0.0, "0 on empty");
+
+        keyboard.add_input(KeyboardInput::from(vec![Key::KeyA]));
+        assert_eq!(axis.update(&keyboard), -1.0);
+
+        keyboard.add_input(KeyboardInput::from(vec![Key::KeyA, Key::KeyD]));
+        assert_eq!(axis.update(&keyboard), 0.0, "opposites cancel");
+    }
+
+    #[test]
+    fn last_pressed_wins() {
+        let mut axis = Axis1d::new(Binding::Key(Key::KeyA), Binding::Key(Key::KeyD))
+            .with_policy(OppositePolicy::LastPressedWins);
+        let mut keyboard = KeyboardSnapshot::new();
+
+        keyboard.add_input(KeyboardInput::from(vec![Key::KeyA]));
+        axis.update(&keyboard);
+        keyboard.add_input(KeyboardInput::from(vec![Key::KeyA, Key::KeyD]));
+        assert_eq!(axis.update(&keyboard), 1.0, "right pressed last");
+
+        keyboard.add_input(KeyboardInput::from(vec![Key::KeyD]));
+        axis.update(&keyboard);
+        keyboard.add_input(KeyboardInput::from(vec![Key::KeyA, Key::KeyD]));
+        assert_eq!(axis.update(&keyboard), -1.0, "left pressed last");
+
+        keyboard.add_input(KeyboardInput::from(vec![Key::KeyA, Key::KeyD]));
+        assert_eq!(axis.update(&keyboard), -1.0, "while both held");
+    }
+
+    #[test]
+    fn axis2d_normalized() {
+        let mut axis = Axis2d::wasd();
+        let mut keyboard = KeyboardSnapshot::new();
+
+        keyboard.add_input(KeyboardInput::from(vec![Key::KeyW]));
+        assert_eq!(
+            axis.update(&keyboard),
+            Vector2::new(0.0, -1.0),
+            "up is negative"
+        );
+
+        keyboard.add_input(KeyboardInput::from(vec![Key::KeyS, Key::KeyD]));
+        let diagonal = axis.update(&keyboard);
+        assert!((diagonal.length() - 1.0).abs() < 1e-6, "{:?}", diagonal);
+        assert!(diagonal.x > 0.0 && diagonal.y > 0.0);
+    }
+
+    #[test]
+    fn axis2d_stick() {
+        let mut axis = Axis2d::arrows().with_stick(Hand::Left);
+        let keyboard = KeyboardSnapshot::new();
+        let mut gamepad = GamepadSnapshot::new();
+
+        let mut input = GamepadInput::new();
+        input.set_axes(Hand::Left, 1000, -1000);
+        gamepad.add_input(input);
+        assert_eq!(
+            axis.update(&(&keyboard, &gamepad)),
+            Vector2::ZERO,
+            "inside the dead zone"
+        );
+
+        let mut input = GamepadInput::new();
+        input.set_axes(Hand::Left, 5000, 0);
+        gamepad.add_input(input);
+        assert_eq!(axis.update(&(&keyboard, &gamepad)), Vector2::new(0.5, 0.0));
+
+        let mut input = GamepadInput::new();
+        input.set_axes(Hand::Left, 10000, 10000);
+        gamepad.add_input(input);
+        let corner = axis.update(&(&keyboard, &gamepad));
+        assert!(
+            (corner.length() - 1.0).abs() < 1e-6,
+            "clamped to 1, was {:?}",
+            corner
+        );
+    }
+
+    #[test]
+    fn axis2d_keys_over_stick() {
+        let mut axis = Axis2d::arrows().with_stick(Hand::Left);
+        let mut keyboard = KeyboardSnapshot::new();
+        let mut gamepad = GamepadSnapshot::new();
+
+        let mut input = GamepadInput::new();
+        input.set_axes(Hand::Left, 10000, 0);
+        gamepad.add_input(input);
+        keyboard.add_input(KeyboardInput::from(vec![Key::ArrowLeft]));
+        assert_eq!(axis.update(&(&keyboard, &gamepad)), Vector2::new(-1.0, 0.0));
+    }
+}
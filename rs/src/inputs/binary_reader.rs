@@ -0,0 +1,188 @@
+use super::InputType;
+use crate::PogpError;
+
+/// Reads little-endian pogp primitives from a byte slice, mirroring the typescript BinaryReader
+pub(crate) struct BinaryReader<'a> {
+    buffer: &'a [u8],
+    offset: usize,
+    bit_offset: u8,
+}
+
+impl<'a> BinaryReader<'a> {
+    pub fn new(buffer: &'a [u8]) -> Self {
+        Self {
+            buffer,
+            offset: 0,
+            bit_offset: 0,
+        }
+    }
+
+    /// reads the type byte that starts every input and checks that it is `expected`
+    pub fn read_input_type(&mut self, expected: InputType) -> Result<(), PogpError> {
+        let actual = self.read_u8()?;
+        if actual != expected as u8 {
+            return Err(PogpError::WrongInputType { expected, actual });
+        }
+        Ok(())
+    }
+
+    pub fn read_bool(&mut self) -> Result<bool, PogpError> {
+        self.check_length(1)?;
+        let ret = self.buffer[self.offset] & (1 << self.bit_offset) > 0;
+        self.bit_offset += 1;
+        if self.bit_offset == 8 {
+            self.bit_offset = 0;
+            self.offset += 1;
+        }
+        Ok(ret)
+    }
+
+    pub fn read_u8(&mut self) -> Result<u8, PogpError> {
+        Ok(self.read_bytes::<1>()?[0])
+    }
+
+    pub fn read_u16(&mut self) -> Result<u16, PogpError> {
+        Ok(u16::from_le_bytes(self.read_bytes()?))
+    }
+
+    pub fn read_u32(&mut self) -> Result<u32, PogpError> {
+        Ok(u32::from_le_bytes(self.read_bytes()?))
+    }
+
+    pub fn read_i32(&mut self) -> Result<i32, PogpError> {
+        Ok(i32::from_le_bytes(self.read_bytes()?))
+    }
+
+    pub fn read_i64(&mut self) -> Result<i64, PogpError> {
+        Ok(i64::from_le_bytes(self.read_bytes()?))
+    }
+
+    /// reads a uint32 byte length followed by that many bytes of utf-8
+    pub fn read_string(&mut self) -> Result<String, PogpError> {
+        let length = self.read_u32()? as usize;
+        self.check_length(length)?;
+        let bytes = &self.buffer[self.offset..self.offset + length];
+        self.offset += length;
+        String::from_utf8(bytes.to_vec()).map_err(|_| PogpError::InvalidString)
+    }
+
+    fn read_bytes<const N: usize>(&mut self) -> Result<[u8; N], PogpError> {
+        // if we were reading bools from the current byte, move to the next byte
+        if self.bit_offset > 0 {
+            self.offset += 1;
+            self.bit_offset = 0;
+        }
+        self.check_length(N)?;
+        let mut bytes = [0; N];
+        bytes.copy_from_slice(&self.buffer[self.offset..self.offset + N]);
+        self.offset += N;
+        Ok(bytes)
+    }
+
+    fn check_length(&self, length: usize) -> Result<(), PogpError> {
+        if self.offset + length > self.buffer.len() {
+            return Err(PogpError::Truncated {
+                expected: self.offset + length,
+                actual: self.buffer.len(),
+            });
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod binary_reader {
+    use super::*;
+
+    #[test]
+    fn read_bool() {
+        let buffer: [u8; 2] = [0b0100_0001, 0b0000_0001];
+        let mut reader = BinaryReader::new(&buffer);
+        assert_eq!(reader.read_bool().unwrap(), true);
+        for _ in 0..5 {
+            assert_eq!(reader.read_bool().unwrap(), false);
+        }
+        assert_eq!(reader.read_bool().unwrap(), true);
+        assert_eq!(reader.read_bool().unwrap(), false);
+        assert_eq!(
+            reader.read_bool().unwrap(),
+            true,
+            "reads into the next byte"
+        );
+    }
+
+    #[test]
+    fn read_numbers() {
+        let buffer: [u8; 15] = [
+            0xff, 0x06, 0x00, 0x10, 0x27, 0x00, 0x00, 0xfe, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff,
+            0xff,
+        ];
+        let mut reader = BinaryReader::new(&buffer);
+        assert_eq!(reader.read_u8().unwrap(), 255);
+        assert_eq!(reader.read_u16().unwrap(), 6);
+        assert_eq!(reader.read_u32().unwrap(), 10000);
+        assert_eq!(reader.read_i64().unwrap(), -2);
+    }
+
+    #[test]
+    fn read_string() {
+        let buffer: [u8; 11] = [
+            0x02, 0x00, 0x00, 0x00, 0x68, 0x69, 0x01, 0x00, 0x00, 0x00, 0x21,
+        ];
+        let mut reader = BinaryReader::new(&buffer);
+        assert_eq!(reader.read_string().unwrap(), "hi");
+        assert_eq!(reader.read_string().unwrap(), "!");
+    }
+
+    #[test]
+    fn read_after_bool() {
+        let buffer: [u8; 2] = [0b0000_0001, 0x04];
+        let mut reader = BinaryReader::new(&buffer);
+        assert_eq!(reader.read_bool().unwrap(), true);
+        assert_eq!(
+            reader.read_u8().unwrap(),
+            4,
+            "skips the rest of the bool byte"
+        );
+    }
+
+    #[test]
+    fn read_input_type() {
+        let buffer: [u8; 1] = [InputType::Mouse as u8];
+        assert_eq!(
+            BinaryReader::new(&buffer).read_input_type(InputType::Keyboard),
+            Err(PogpError::WrongInputType {
+                expected: InputType::Keyboard,
+                actual: 3
+            })
+        );
+        assert_eq!(
+            BinaryReader::new(&[]).read_input_type(InputType::Mouse),
+            Err(PogpError::Truncated {
+                expected: 1,
+                actual: 0
+            })
+        );
+    }
+
+    #[test]
+    fn truncated() {
+        let buffer: [u8; 3] = [0x01, 0x02, 0x03];
+        let mut reader = BinaryReader::new(&buffer);
+        reader.read_u16().unwrap();
+        assert_eq!(
+            reader.read_u32(),
+            Err(PogpError::Truncated {
+                expected: 6,
+                actual: 3
+            })
+        );
+    }
+
+    #[test]
+    fn invalid_string() {
+        let buffer: [u8; 5] = [0x01, 0x00, 0x00, 0x00, 0xff];
+        let mut reader = BinaryReader::new(&buffer);
+        assert_eq!(reader.read_string(), Err(PogpError::InvalidString));
+    }
+}
@@ -0,0 +1,104 @@
+/// Writes little-endian pogp primitives into a byte slice, mirroring the typescript BinaryWriter
+pub(crate) struct BinaryWriter<'a> {
+    buffer: &'a mut [u8],
+    offset: usize,
+    bit_offset: u8,
+}
+
+impl<'a> BinaryWriter<'a> {
+    pub fn new(buffer: &'a mut [u8]) -> Self {
+        Self {
+            buffer,
+            offset: 0,
+            bit_offset: 0,
+        }
+    }
+
+    pub fn write_bool(&mut self, value: bool) {
+        if value {
+            self.buffer[self.offset] |= 1 << self.bit_offset;
+        } else {
+            self.buffer[self.offset] &= !(1 << self.bit_offset);
+        }
+        self.bit_offset += 1;
+        if self.bit_offset == 8 {
+            self.bit_offset = 0;
+            self.offset += 1;
+        }
+    }
+
+    pub fn write_u8(&mut self, value: u8) {
+        self.write_bytes(&[value]);
+    }
+
+    pub fn write_u16(&mut self, value: u16) {
+        self.write_bytes(&value.to_le_bytes());
+    }
+
+    pub fn write_u32(&mut self, value: u32) {
+        self.write_bytes(&value.to_le_bytes());
+    }
+
+    pub fn write_i32(&mut self, value: i32) {
+        self.write_bytes(&value.to_le_bytes());
+    }
+
+    pub fn write_i64(&mut self, value: i64) {
+        self.write_bytes(&value.to_le_bytes());
+    }
+
+    /// writes a uint32 byte length followed by the utf-8 bytes of `value`
+    pub fn write_string(&mut self, value: &str) {
+        self.write_u32(value.len() as u32);
+        self.write_bytes(value.as_bytes());
+    }
+
+    fn write_bytes(&mut self, bytes: &[u8]) {
+        // if we were writing bools into the current byte, move to the next byte
+        if self.bit_offset > 0 {
+            self.offset += 1;
+            self.bit_offset = 0;
+        }
+        self.buffer[self.offset..self.offset + bytes.len()].copy_from_slice(bytes);
+        self.offset += bytes.len();
+    }
+}
+
+#[cfg(test)]
+mod binary_writer {
+    use super::super::binary_reader::BinaryReader;
+    use super::*;
+
+    #[test]
+    fn write_bool() {
+        let mut buffer = [0xff; 2];
+        let mut writer = BinaryWriter::new(&mut buffer);
+        writer.write_bool(true);
+        for _ in 0..7 {
+            writer.write_bool(false);
+        }
+        writer.write_bool(true);
+        assert_eq!(buffer[0], 0b0000_0001, "clears unset bits");
+        assert_eq!(buffer[1] & 1, 1);
+    }
+
+    #[test]
+    fn round_trip() {
+        let mut buffer = [0; 26];
+        let mut writer = BinaryWriter::new(&mut buffer);
+        writer.write_u8(1);
+        writer.write_u16(512);
+        writer.write_u32(10000);
+        writer.write_i64(-2147483647);
+        writer.write_i32(-1);
+        writer.write_string("é");
+
+        let mut reader = BinaryReader::new(&buffer);
+        assert_eq!(reader.read_u8().unwrap(), 1);
+        assert_eq!(reader.read_u16().unwrap(), 512);
+        assert_eq!(reader.read_u32().unwrap(), 10000);
+        assert_eq!(reader.read_i64().unwrap(), -2147483647);
+        assert_eq!(reader.read_i32().unwrap(), -1);
+        assert_eq!(reader.read_string().unwrap(), "é");
+    }
+}
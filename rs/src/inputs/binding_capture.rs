@@ -0,0 +1,164 @@
+use super::{Binding, BindingSource};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum CaptureState {
+    /// waiting for an input
+    Pending,
+    /// the first new input pressed
+    Captured(Binding),
+    /// the cancel binding was pressed
+    Cancelled,
+}
+
+/// BindingCapture listens for the next input pressed, for rebinding controls from a menu.
+///
+/// Inputs held when the capture starts are ignored until they're released, so the key that
+/// opened the capture isn't captured. If several inputs are pressed on the same frame the first
+/// reported by the source wins, keyboard keys in id order.
+#[derive(Clone, Debug)]
+pub struct BindingCapture {
+    cancel: Option<Binding>,
+    state: CaptureState,
+    /// inputs held when the capture started that haven't been released yet
+    ignored: Vec<Binding>,
+    /// scratch space for the inputs held this frame and last
+    held: Vec<Binding>,
+    previous: Vec<Binding>,
+}
+
+impl BindingCapture {
+    /// starts capturing, ignoring whatever `source` has held on its latest input
+    pub fn start(source: &impl BindingSource) -> Self {
+        let mut ignored = Vec::new();
+        source.held_bindings(0, &mut ignored);
+        Self {
+            cancel: None,
+            state: CaptureState::Pending,
+            ignored,
+            held: Vec::new(),
+            previous: Vec::new(),
+        }
+    }
+
+    /// pressing `cancel` ends the capture without capturing, e.g. Escape
+    pub fn with_cancel(self, cancel: Binding) -> Self {
+        Self {
+            cancel: Some(cancel),
+            ..self
+        }
+    }
+
+    pub fn state(&self) -> CaptureState {
+        self.state
+    }
+
+    /// reads the latest input from `source`, call once per frame after adding inputs to it.
+    /// Once the capture is captured or cancelled it stays that way.
+    pub fn update(&mut self, source: &impl BindingSource) -> CaptureState {
+        if self.state != CaptureState::Pending {
+            return self.state;
+        }
+        self.held.clear();
+        self.previous.clear();
+        source.held_bindings(0, &mut self.held);
+        source.held_bindings(1, &mut self.previous);
+
+        let held = &self.held;
+        self.ignored.retain(|binding| held.contains(binding));
+
+        let mut pressed = self
+            .held
+            .iter()
+            .filter(|binding| !self.previous.contains(binding) && !self.ignored.contains(binding));
+        if let Some(first) = pressed.next() {
+            // cancel wins over anything pressed on the same frame
+            self.state = match self.cancel {
+                Some(cancel) if *first == cancel || pressed.any(|b| *b == cancel) => {
+                    CaptureState::Cancelled
+                }
+                _ => CaptureState::Captured(*first),
+            };
+        }
+        self.state
+    }
+}
+
+#[cfg(test)]
+mod binding_capture {
+    use super::*;
+    use crate::inputs::{
+        ButtonPosition, GamepadInput, GamepadSnapshot, Key, KeyboardInput, KeyboardSnapshot,
+    };
+
+    #[test]
+    fn captures_next_press() {
+        let mut keyboard = KeyboardSnapshot::new();
+        let mut capture = BindingCapture::start(&keyboard);
+        assert_eq!(capture.update(&keyboard), CaptureState::Pending);
+
+        keyboard.add_input(KeyboardInput::from(vec![Key::KeyQ]));
+        assert_eq!(
+            capture.update(&keyboard),
+            CaptureState::Captured(Binding::Key(Key::KeyQ))
+        );
+
+        keyboard.add_input(KeyboardInput::from(vec![Key::KeyE]));
+        assert_eq!(
+            capture.update(&keyboard),
+            CaptureState::Captured(Binding::Key(Key::KeyQ)),
+            "stays captured"
+        );
+    }
+
+    #[test]
+    fn ignores_held_at_start() {
+        let mut keyboard = KeyboardSnapshot::new();
+        keyboard.add_input(KeyboardInput::from(vec![Key::Enter]));
+        let mut capture = BindingCapture::start(&keyboard);
+        assert_eq!(
+            capture.update(&keyboard),
+            CaptureState::Pending,
+            "ignores the key that started capturing"
+        );
+
+        keyboard.add_input(KeyboardInput::from(vec![Key::Enter]));
+        assert_eq!(capture.update(&keyboard), CaptureState::Pending);
+
+        keyboard.add_input(Default::default());
+        capture.update(&keyboard);
+        keyboard.add_input(KeyboardInput::from(vec![Key::Enter]));
+        assert_eq!(
+            capture.update(&keyboard),
+            CaptureState::Captured(Binding::Key(Key::Enter)),
+            "captures it once it's pressed again"
+        );
+    }
+
+    #[test]
+    fn cancel() {
+        let mut keyboard = KeyboardSnapshot::new();
+        let mut capture = BindingCapture::start(&keyboard).with_cancel(Binding::Key(Key::Escape));
+
+        keyboard.add_input(KeyboardInput::from(vec![Key::Escape, Key::ArrowDown]));
+        assert_eq!(
+            capture.update(&keyboard),
+            CaptureState::Cancelled,
+            "cancel wins on the same frame"
+        );
+    }
+
+    #[test]
+    fn gamepad() {
+        let keyboard = KeyboardSnapshot::new();
+        let mut gamepad = GamepadSnapshot::new();
+        let mut capture = BindingCapture::start(&(&keyboard, &gamepad));
+
+        let mut input = GamepadInput::new();
+        input.set_button(ButtonPosition::RightShoulderBack, 10000);
+        gamepad.add_input(input);
+        assert_eq!(
+            capture.update(&(&keyboard, &gamepad)),
+            CaptureState::Captured(Binding::Gamepad(ButtonPosition::RightShoulderBack))
+        );
+    }
+}
@@ -0,0 +1,607 @@
+use std::fmt::{self, Write};
+use std::str::FromStr;
+
+use super::{ActionMap, Binding};
+use crate::PogpError;
+
+/// the newest binding profile version this library reads, and the one it writes
+pub const BINDING_PROFILE_VERSION: u32 = 1;
+
+/// the name of `Binding::Mouse` in a profile
+const MOUSE_BUTTON: &str = "Button";
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Device {
+    Keyboard,
+    Gamepad,
+    Mouse,
+}
+
+impl Device {
+    const ALL: [Device; 3] = [Device::Keyboard, Device::Gamepad, Device::Mouse];
+
+    fn of(binding: Binding) -> Device {
+        match binding {
+            Binding::Key(_) => Device::Keyboard,
+            Binding::Gamepad(_) => Device::Gamepad,
+            Binding::Mouse => Device::Mouse,
+        }
+    }
+
+    fn section(&self) -> &'static str {
+        match self {
+            Device::Keyboard => "keyboard",
+            Device::Gamepad => "gamepad",
+            Device::Mouse => "mouse",
+        }
+    }
+
+    fn binding(&self, name: &str) -> Result<Binding, PogpError> {
+        match self {
+            Device::Keyboard => name.parse().map(Binding::Key),
+            Device::Gamepad => name.parse().map(Binding::Gamepad),
+            Device::Mouse if name == MOUSE_BUTTON => Ok(Binding::Mouse),
+            Device::Mouse => Err(syntax(format!(
+                "{:?} for a mouse binding, but it was {:?}",
+                MOUSE_BUTTON, name
+            ))),
+        }
+    }
+}
+
+fn binding_name(binding: Binding) -> &'static str {
+    match binding {
+        Binding::Key(key) => key.code(),
+        Binding::Gamepad(position) => position.name(),
+        Binding::Mouse => MOUSE_BUTTON,
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+struct Entry {
+    device: Device,
+    action: String,
+    bindings: Vec<Binding>,
+}
+
+/// BindingProfile is a player's bindings saved as text, so they survive restarts and can be
+/// shared between clients in any language.
+///
+/// It's a small subset of TOML, keys use their w3c UIEvents code and gamepad buttons their
+/// `ButtonPosition` name:
+///
+/// ```toml
+/// version = 1
+///
+/// [keyboard]
+/// Jump = ["Space", "KeyW"]
+/// Fire = []
+///
+/// [gamepad]
+/// Jump = ["RightFaceBottom"]
+///
+/// [mouse]
+/// Fire = ["Button"]
+/// ```
+///
+/// Profiles are merged over the game's defaults with `apply`, so an action a profile doesn't
+/// mention for a device keeps its default bindings for that device.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct BindingProfile {
+    entries: Vec<Entry>,
+}
+
+impl BindingProfile {
+    /// every action of `actions` in every device section, including unbound ones so they stay
+    /// unbound when applied over defaults
+    pub fn from_action_map<A: Copy + PartialEq + fmt::Display>(actions: &ActionMap<A>) -> Self {
+        let entries = Device::ALL
+            .into_iter()
+            .flat_map(|device| {
+                actions.actions().map(move |action| Entry {
+                    device,
+                    action: action.to_string(),
+                    bindings: actions
+                        .bindings(action)
+                        .iter()
+                        .copied()
+                        .filter(|binding| Device::of(*binding) == device)
+                        .collect(),
+                })
+            })
+            .collect();
+        Self { entries }
+    }
+
+    /// replaces the bindings of `actions` for each action and device in the profile, leaving
+    /// everything else as it was.
+    ///
+    /// Actions that don't parse, e.g. ones renamed or removed by a game update, are skipped so
+    /// the rest of the profile still applies. Their names are returned, each once.
+    pub fn apply<A: Copy + PartialEq + FromStr>(&self, actions: &mut ActionMap<A>) -> Vec<String> {
+        let mut unknown: Vec<String> = Vec::new();
+        for entry in &self.entries {
+            let Ok(action) = entry.action.parse::<A>() else {
+                if !unknown.contains(&entry.action) {
+                    unknown.push(entry.action.clone());
+                }
+                continue;
+            };
+            for binding in actions.bindings(action).to_vec() {
+                if Device::of(binding) == entry.device {
+                    actions.unbind(action, binding);
+                }
+            }
+            for binding in &entry.bindings {
+                actions.bind(action, *binding);
+            }
+        }
+        unknown
+    }
+}
+
+impl FromStr for BindingProfile {
+    type Err = PogpError;
+
+    fn from_str(profile: &str) -> Result<Self, Self::Err> {
+        let mut parser = Parser::default();
+        let mut lines = 0;
+        for (index, line) in profile.lines().enumerate() {
+            lines = index + 1;
+            parser
+                .line(strip_comment(line).trim())
+                .map_err(|error| PogpError::InvalidProfile {
+                    line: index + 1,
+                    error: Box::new(error),
+                })?;
+        }
+        if parser.version.is_none() {
+            return Err(PogpError::InvalidProfile {
+                line: lines.max(1),
+                error: Box::new(syntax(format!("version = {}", BINDING_PROFILE_VERSION))),
+            });
+        }
+        Ok(BindingProfile {
+            entries: parser.entries,
+        })
+    }
+}
+
+impl fmt::Display for BindingProfile {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "version = {}", BINDING_PROFILE_VERSION)?;
+        for device in Device::ALL {
+            let mut entries = self
+                .entries
+                .iter()
+                .filter(|entry| entry.device == device)
+                .peekable();
+            if entries.peek().is_none() {
+                continue;
+            }
+            write!(f, "\n[{}]\n", device.section())?;
+            for entry in entries {
+                if !entry.action.is_empty() && entry.action.chars().all(is_bare) {
+                    f.write_str(&entry.action)?;
+                } else {
+                    write_string(f, &entry.action)?;
+                }
+                f.write_str(" = [")?;
+                for (i, binding) in entry.bindings.iter().enumerate() {
+                    if i > 0 {
+                        f.write_str(", ")?;
+                    }
+                    write_string(f, binding_name(*binding))?;
+                }
+                f.write_str("]\n")?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[derive(Default)]
+struct Parser {
+    version: Option<u32>,
+    device: Option<Device>,
+    /// sections already read, TOML doesn't allow a section twice
+    sections: Vec<Device>,
+    entries: Vec<Entry>,
+}
+
+impl Parser {
+    /// reads one line with its comment and surrounding whitespace removed
+    fn line(&mut self, line: &str) -> Result<(), PogpError> {
+        if line.is_empty() {
+            return Ok(());
+        }
+
+        if let Some(section) = line.strip_prefix('[') {
+            let section = section
+                .strip_suffix(']')
+                .ok_or_else(|| syntax(format!("`]` at the end of `{}`", line)))?
+                .trim();
+            if self.version.is_none() {
+                return Err(syntax(format!(
+                    "version = {} before any section",
+                    BINDING_PROFILE_VERSION
+                )));
+            }
+            let device = Device::ALL
+                .into_iter()
+                .find(|device| device.section() == section)
+                .ok_or_else(|| {
+                    syntax(format!(
+                        "[keyboard], [gamepad] or [mouse], but it was [{}]",
+                        section
+                    ))
+                })?;
+            if self.sections.contains(&device) {
+                return Err(syntax(format!(
+                    "each section once, but [{}] was repeated",
+                    section
+                )));
+            }
+            self.sections.push(device);
+            self.device = Some(device);
+            return Ok(());
+        }
+
+        let (name, value) = split_key(line)?;
+        let Some(device) = self.device else {
+            return self.version(&name, value);
+        };
+        if self
+            .entries
+            .iter()
+            .any(|entry| entry.device == device && entry.action == name)
+        {
+            return Err(syntax(format!(
+                "each action once per section, but `{}` was repeated",
+                name
+            )));
+        }
+        let bindings = parse_list(value)?
+            .iter()
+            .map(|binding| device.binding(binding))
+            .collect::<Result<_, _>>()?;
+        self.entries.push(Entry {
+            device,
+            action: name,
+            bindings,
+        });
+        Ok(())
+    }
+
+    fn version(&mut self, name: &str, value: &str) -> Result<(), PogpError> {
+        if name != "version" {
+            return Err(syntax(format!(
+                "only version before the first section, but it was `{}`",
+                name
+            )));
+        }
+        if self.version.is_some() {
+            return Err(syntax("one version, but it was set twice".to_string()));
+        }
+        let version = value
+            .parse::<u32>()
+            .map_err(|_| syntax(format!("version to be a number, but it was `{}`", value)))?;
+        if version == 0 || version > BINDING_PROFILE_VERSION {
+            return Err(PogpError::UnsupportedProfileVersion(version));
+        }
+        self.version = Some(version);
+        Ok(())
+    }
+}
+
+fn syntax(reason: String) -> PogpError {
+    PogpError::ProfileSyntax(reason)
+}
+
+/// characters allowed in an unquoted TOML key
+fn is_bare(c: char) -> bool {
+    c.is_ascii_alphanumeric() || c == '_' || c == '-'
+}
+
+fn strip_comment(line: &str) -> &str {
+    let mut in_string = false;
+    let mut escaped = false;
+    for (i, c) in line.char_indices() {
+        match c {
+            _ if escaped => escaped = false,
+            '\\' if in_string => escaped = true,
+            '"' => in_string = !in_string,
+            '#' if !in_string => return &line[..i],
+            _ => {}
+        }
+    }
+    line
+}
+
+/// splits `name = value` into the name and the trimmed value
+fn split_key(line: &str) -> Result<(String, &str), PogpError> {
+    let (name, rest) = if line.starts_with('"') {
+        parse_string(line)?
+    } else {
+        let end = line.find(|c| !is_bare(c)).unwrap_or(line.len());
+        (line[..end].to_string(), &line[end..])
+    };
+    match rest.trim_start().strip_prefix('=') {
+        Some(value) if !name.is_empty() => Ok((name, value.trim())),
+        _ => Err(syntax(format!("`name = value`, but it was `{}`", line))),
+    }
+}
+
+/// reads a quoted string from the start of `value`, returning it and the rest of `value`
+fn parse_string(value: &str) -> Result<(String, &str), PogpError> {
+    let mut string = String::new();
+    let mut chars = value.char_indices().skip(1);
+    while let Some((i, c)) = chars.next() {
+        match c {
+            '"' => return Ok((string, &value[i + 1..])),
+            '\\' => match chars.next() {
+                Some((_, escaped @ ('"' | '\\'))) => string.push(escaped),
+                _ => return Err(syntax(format!("only \\\" and \\\\ escapes in `{}`", value))),
+            },
+            c => string.push(c),
+        }
+    }
+    Err(syntax(format!("a closing quote in `{}`", value)))
+}
+
+/// reads a one line list of strings like `["KeyW", "ArrowUp"]`
+fn parse_list(value: &str) -> Result<Vec<String>, PogpError> {
+    let invalid = || {
+        syntax(format!(
+            "a list like [\"KeyW\", \"ArrowUp\"], but it was `{}`",
+            value
+        ))
+    };
+    let mut rest = value.strip_prefix('[').ok_or_else(invalid)?.trim_start();
+    let mut items = Vec::new();
+    loop {
+        if let Some(after) = rest.strip_prefix(']') {
+            return match after.trim() {
+                "" => Ok(items),
+                _ => Err(invalid()),
+            };
+        }
+        if !rest.starts_with('"') {
+            return Err(invalid());
+        }
+        let (item, after) = parse_string(rest)?;
+        items.push(item);
+        rest = after.trim_start();
+        if let Some(after) = rest.strip_prefix(',') {
+            rest = after.trim_start();
+        } else if !rest.starts_with(']') {
+            return Err(invalid());
+        }
+    }
+}
+
+fn write_string(f: &mut fmt::Formatter<'_>, string: &str) -> fmt::Result {
+    f.write_char('"')?;
+    for c in string.chars() {
+        if c == '"' || c == '\\' {
+            f.write_char('\\')?;
+        }
+        f.write_char(c)?;
+    }
+    f.write_char('"')
+}
+
+#[cfg(test)]
+mod binding_profile {
+    use super::*;
+    use crate::inputs::{ButtonPosition, Key};
+
+    #[derive(Clone, Copy, Debug, PartialEq, Eq)]
+    enum Action {
+        Jump,
+        Fire,
+    }
+
+    impl fmt::Display for Action {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            fmt::Debug::fmt(self, f)
+        }
+    }
+
+    impl FromStr for Action {
+        type Err = ();
+
+        fn from_str(action: &str) -> Result<Self, Self::Err> {
+            match action {
+                "Jump" => Ok(Action::Jump),
+                "Fire" => Ok(Action::Fire),
+                _ => Err(()),
+            }
+        }
+    }
+
+    fn defaults() -> ActionMap<Action> {
+        let mut actions = ActionMap::new();
+        actions.bind(Action::Jump, Binding::Key(Key::Space));
+        actions.bind(
+            Action::Jump,
+            Binding::Gamepad(ButtonPosition::RightFaceBottom),
+        );
+        actions.bind(Action::Fire, Binding::Key(Key::KeyF));
+        actions.bind(Action::Fire, Binding::Mouse);
+        actions
+    }
+
+    #[test]
+    fn to_string() {
+        let mut actions = defaults();
+        actions.unbind(Action::Fire, Binding::Key(Key::KeyF));
+        assert_eq!(
+            BindingProfile::from_action_map(&actions).to_string(),
+            "version = 1
+
+[keyboard]
+Jump = [\"Space\"]
+Fire = []
+
+[gamepad]
+Jump = [\"RightFaceBottom\"]
+Fire = []
+
+[mouse]
+Jump = []
+Fire = [\"Button\"]
+"
+        );
+    }
+
+    #[test]
+    fn round_trip() {
+        let mut actions = defaults();
+        actions.bind(Action::Jump, Binding::Key(Key::ArrowUp));
+        actions.clear(Action::Fire);
+        let profile = BindingProfile::from_action_map(&actions);
+        assert_eq!(profile.to_string().parse(), Ok(profile.clone()));
+
+        let mut loaded = defaults();
+        assert_eq!(profile.apply(&mut loaded), Vec::<String>::new());
+        assert_eq!(
+            loaded.bindings(Action::Jump),
+            &[
+                Binding::Key(Key::Space),
+                Binding::Key(Key::ArrowUp),
+                Binding::Gamepad(ButtonPosition::RightFaceBottom),
+            ]
+        );
+        assert_eq!(loaded.bindings(Action::Fire), &[], "stays unbound");
+    }
+
+    #[test]
+    fn merges_over_defaults() {
+        let profile: BindingProfile = "
+            # rebound in the options menu
+            version = 1
+
+            [keyboard]
+            Jump = [ \"KeyW\", ] # trailing comma
+        "
+        .parse()
+        .unwrap();
+
+        let mut actions = defaults();
+        assert_eq!(profile.apply(&mut actions), Vec::<String>::new());
+        assert_eq!(
+            actions.bindings(Action::Jump),
+            &[
+                Binding::Gamepad(ButtonPosition::RightFaceBottom),
+                Binding::Key(Key::KeyW),
+            ],
+            "keeps gamepad defaults"
+        );
+        assert_eq!(
+            actions.bindings(Action::Fire),
+            defaults().bindings(Action::Fire),
+            "keeps actions that aren't mentioned"
+        );
+    }
+
+    #[test]
+    fn unknown_key() {
+        let profile = "version = 1\n[keyboard]\nJump = [\"Space\", \"Spcae\"]";
+        let error = profile.parse::<BindingProfile>().unwrap_err();
+        assert_eq!(
+            error,
+            PogpError::InvalidProfile {
+                line: 3,
+                error: Box::new(PogpError::UnknownKeyCode("Spcae".to_string())),
+            }
+        );
+        assert_eq!(
+            error.to_string(),
+            "Invalid binding profile on line 3: Unknown key code \"Spcae\""
+        );
+
+        let profile = "version = 1\n[gamepad]\nJump = [\"Cross\"]";
+        assert_eq!(
+            profile.parse::<BindingProfile>(),
+            Err(PogpError::InvalidProfile {
+                line: 3,
+                error: Box::new(PogpError::UnknownButtonName("Cross".to_string())),
+            })
+        );
+    }
+
+    #[test]
+    fn unknown_action() {
+        let profile: BindingProfile =
+            "version = 1\n[keyboard]\nDash = []\n[mouse]\nFire = []\nDash = [\"Button\"]"
+                .parse()
+                .unwrap();
+        let mut actions = defaults();
+        assert_eq!(
+            profile.apply(&mut actions),
+            vec!["Dash".to_string()],
+            "returns removed actions once"
+        );
+        assert_eq!(
+            actions.bindings(Action::Fire),
+            &[Binding::Key(Key::KeyF)],
+            "applies the rest"
+        );
+    }
+
+    #[test]
+    fn version() {
+        assert_eq!(
+            "version = 2\n[keyboard]".parse::<BindingProfile>(),
+            Err(PogpError::InvalidProfile {
+                line: 1,
+                error: Box::new(PogpError::UnsupportedProfileVersion(2)),
+            })
+        );
+        assert_eq!(
+            "[keyboard]\nJump = []".parse::<BindingProfile>(),
+            Err(PogpError::InvalidProfile {
+                line: 1,
+                error: Box::new(PogpError::ProfileSyntax(
+                    "version = 1 before any section".to_string()
+                )),
+            })
+        );
+        assert_eq!(
+            "".parse::<BindingProfile>().map_err(|e| e.to_string()),
+            Err("Invalid binding profile on line 1: Expected version = 1".to_string())
+        );
+    }
+
+    #[test]
+    fn syntax_errors() {
+        let cases = [
+            "[joystick]",
+            "Jump = [\"Space\"",
+            "Jump = \"Space\"",
+            "Jump [\"Space\"]",
+            "Jump = [\"Space]",
+            "Jump = [\"Space\" \"KeyW\"]",
+            "[mouse",
+            "Jump = []\nJump = [\"Space\"]",
+            "Jump = []\n\"Jump\" = []",
+            "[mouse]\n[keyboard]",
+        ];
+        for case in cases {
+            let profile = format!("version = 1\n[keyboard]\n{}", case);
+            let last_line = 2 + case.lines().count();
+            let error = profile.parse::<BindingProfile>().unwrap_err();
+            assert!(
+                matches!(
+                    &error,
+                    PogpError::InvalidProfile { line, error }
+                        if *line == last_line && matches!(**error, PogpError::ProfileSyntax(_))
+                ),
+                "{:?} was {:?}",
+                case,
+                error
+            );
+        }
+    }
+}
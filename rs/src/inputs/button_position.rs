@@ -0,0 +1,188 @@
+use std::fmt;
+use std::str::FromStr;
+
+use crate::PogpError;
+
+/// Generic positional identifier for a gamepad button, e.g. `RightFaceBottom` is A on an xbox
+/// controller and Cross on a playstation controller.
+///
+/// Discriminants are part of the pogp binary format and match the typescript `ButtonPosition`.
+#[repr(u8)]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum ButtonPosition {
+    #[default]
+    Null = 0,
+
+    LeftFaceTop = 1,
+    LeftFaceRight = 2,
+    LeftFaceBottom = 3,
+    LeftFaceLeft = 4,
+    LeftShoulderFront = 5,
+    LeftShoulderBack = 6,
+    LeftThumbstick = 7,
+
+    RightFaceTop = 8,
+    RightFaceRight = 9,
+    RightFaceBottom = 10,
+    RightFaceLeft = 11,
+    RightShoulderFront = 12,
+    RightShoulderBack = 13,
+    RightThumbstick = 14,
+
+    Middle = 15,
+    MiddleLeft = 16,
+    MiddleRight = 17,
+}
+
+impl ButtonPosition {
+    /// every position except Null, in discriminant order
+    pub const ALL: [ButtonPosition; 17] = [
+        ButtonPosition::LeftFaceTop,
+        ButtonPosition::LeftFaceRight,
+        ButtonPosition::LeftFaceBottom,
+        ButtonPosition::LeftFaceLeft,
+        ButtonPosition::LeftShoulderFront,
+        ButtonPosition::LeftShoulderBack,
+        ButtonPosition::LeftThumbstick,
+        ButtonPosition::RightFaceTop,
+        ButtonPosition::RightFaceRight,
+        ButtonPosition::RightFaceBottom,
+        ButtonPosition::RightFaceLeft,
+        ButtonPosition::RightShoulderFront,
+        ButtonPosition::RightShoulderBack,
+        ButtonPosition::RightThumbstick,
+        ButtonPosition::Middle,
+        ButtonPosition::MiddleLeft,
+        ButtonPosition::MiddleRight,
+    ];
+
+    /// the variant name, e.g. "RightFaceBottom", Null is the empty string
+    pub fn name(&self) -> &'static str {
+        match self {
+            ButtonPosition::Null => "",
+            ButtonPosition::LeftFaceTop => "LeftFaceTop",
+            ButtonPosition::LeftFaceRight => "LeftFaceRight",
+            ButtonPosition::LeftFaceBottom => "LeftFaceBottom",
+            ButtonPosition::LeftFaceLeft => "LeftFaceLeft",
+            ButtonPosition::LeftShoulderFront => "LeftShoulderFront",
+            ButtonPosition::LeftShoulderBack => "LeftShoulderBack",
+            ButtonPosition::LeftThumbstick => "LeftThumbstick",
+            ButtonPosition::RightFaceTop => "RightFaceTop",
+            ButtonPosition::RightFaceRight => "RightFaceRight",
+            ButtonPosition::RightFaceBottom => "RightFaceBottom",
+            ButtonPosition::RightFaceLeft => "RightFaceLeft",
+            ButtonPosition::RightShoulderFront => "RightShoulderFront",
+            ButtonPosition::RightShoulderBack => "RightShoulderBack",
+            ButtonPosition::RightThumbstick => "RightThumbstick",
+            ButtonPosition::Middle => "Middle",
+            ButtonPosition::MiddleLeft => "MiddleLeft",
+            ButtonPosition::MiddleRight => "MiddleRight",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<ButtonPosition> {
+        ButtonPosition::ALL
+            .iter()
+            .copied()
+            .find(|position| position.name() == name)
+    }
+}
+
+impl FromStr for ButtonPosition {
+    type Err = PogpError;
+
+    fn from_str(name: &str) -> Result<Self, Self::Err> {
+        ButtonPosition::from_name(name)
+            .ok_or_else(|| PogpError::UnknownButtonName(name.to_string()))
+    }
+}
+
+impl fmt::Display for ButtonPosition {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+impl From<ButtonPosition> for u8 {
+    fn from(position: ButtonPosition) -> Self {
+        position as u8
+    }
+}
+
+impl TryFrom<u8> for ButtonPosition {
+    type Error = PogpError;
+
+    fn try_from(position: u8) -> Result<Self, Self::Error> {
+        match position {
+            0 => Ok(ButtonPosition::Null),
+
+            1 => Ok(ButtonPosition::LeftFaceTop),
+            2 => Ok(ButtonPosition::LeftFaceRight),
+            3 => Ok(ButtonPosition::LeftFaceBottom),
+            4 => Ok(ButtonPosition::LeftFaceLeft),
+            5 => Ok(ButtonPosition::LeftShoulderFront),
+            6 => Ok(ButtonPosition::LeftShoulderBack),
+            7 => Ok(ButtonPosition::LeftThumbstick),
+
+            8 => Ok(ButtonPosition::RightFaceTop),
+            9 => Ok(ButtonPosition::RightFaceRight),
+            10 => Ok(ButtonPosition::RightFaceBottom),
+            11 => Ok(ButtonPosition::RightFaceLeft),
+            12 => Ok(ButtonPosition::RightShoulderFront),
+            13 => Ok(ButtonPosition::RightShoulderBack),
+            14 => Ok(ButtonPosition::RightThumbstick),
+
+            15 => Ok(ButtonPosition::Middle),
+            16 => Ok(ButtonPosition::MiddleLeft),
+            17 => Ok(ButtonPosition::MiddleRight),
+
+            _ => Err(PogpError::UnknownButtonPosition(position)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod button_position {
+    use super::*;
+
+    #[test]
+    fn round_trip() {
+        for value in 0..=17 {
+            let position = ButtonPosition::try_from(value).unwrap();
+            assert_eq!(u8::from(position), value);
+        }
+    }
+
+    #[test]
+    fn discriminants() {
+        // these are fixed by the binary format, see README.md#button-position-enum
+        assert_eq!(ButtonPosition::LeftFaceTop as u8, 1);
+        assert_eq!(ButtonPosition::RightFaceTop as u8, 8);
+        assert_eq!(ButtonPosition::Middle as u8, 15);
+        assert_eq!(ButtonPosition::MiddleRight as u8, 17);
+    }
+
+    #[test]
+    fn try_from_unknown() {
+        assert_eq!(
+            ButtonPosition::try_from(18),
+            Err(PogpError::UnknownButtonPosition(18))
+        );
+        assert_eq!(
+            ButtonPosition::try_from(255),
+            Err(PogpError::UnknownButtonPosition(255))
+        );
+    }
+
+    #[test]
+    fn names() {
+        for position in ButtonPosition::ALL {
+            assert_eq!(position.to_string().parse(), Ok(position));
+        }
+        assert_eq!(
+            "Cross".parse::<ButtonPosition>(),
+            Err(PogpError::UnknownButtonName("Cross".to_string()))
+        );
+        assert_eq!(ButtonPosition::from_name(""), None, "Null has no name");
+    }
+}
@@ -0,0 +1,229 @@
+use super::binary_reader::BinaryReader;
+use super::binary_writer::BinaryWriter;
+use super::InputType;
+use crate::PogpError;
+
+/// fields and values are prefixed by a uint16 length
+const MAX_LENGTH: usize = u16::MAX as usize;
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct CustomField {
+    pub id: String,
+    pub values: Vec<i32>,
+}
+
+/// CustomInput carries any device that doesn't fit the standard input types, e.g. a flightstick,
+/// as a list of named fields of integer values.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct CustomInput {
+    id: String,
+    fields: Vec<CustomField>,
+}
+
+impl CustomInput {
+    pub fn new(id: &str) -> Self {
+        Self {
+            id: id.to_string(),
+            fields: Vec::new(),
+        }
+    }
+
+    pub fn id(&self) -> &str {
+        &self.id
+    }
+
+    pub fn fields(&self) -> &[CustomField] {
+        &self.fields
+    }
+
+    /// errors with `PogpError::TooMany` if there are more values, or this would add more
+    /// fields, than a uint16 length can hold
+    pub fn set_field(&mut self, id: &str, values: &[i32]) -> Result<(), PogpError> {
+        if values.len() > MAX_LENGTH {
+            return Err(PogpError::TooMany {
+                what: "values",
+                max: MAX_LENGTH,
+                actual: values.len(),
+            });
+        }
+        let field_count = self.fields.len();
+        match self.fields.iter_mut().find(|f| f.id == id) {
+            Some(field) => {
+                field.values.clear();
+                field.values.extend_from_slice(values);
+            }
+            None if field_count >= MAX_LENGTH => {
+                return Err(PogpError::TooMany {
+                    what: "fields",
+                    max: MAX_LENGTH,
+                    actual: field_count + 1,
+                })
+            }
+            None => self.fields.push(CustomField {
+                id: id.to_string(),
+                values: values.to_vec(),
+            }),
+        }
+        Ok(())
+    }
+
+    /// values of the field named `id`
+    pub fn field(&self, id: &str) -> Option<&[i32]> {
+        self.fields
+            .iter()
+            .find(|f| f.id == id)
+            .map(|f| f.values.as_slice())
+    }
+
+    /// number of bytes this input occupies in the pogp binary format
+    pub fn byte_length(&self) -> usize {
+        // type, id, fields.length
+        let header = 1 + 4 + self.id.len() + 2;
+        self.fields.iter().fold(header, |length, field| {
+            // id, values.length, values
+            length + 4 + field.id.len() + 2 + 4 * field.values.len()
+        })
+    }
+
+    /// writes this input in the pogp binary format to the start of `buffer`
+    pub fn write_to(&self, buffer: &mut [u8]) {
+        if buffer.len() < self.byte_length() {
+            panic!(
+                "Expected buffer of at least {} bytes, but it was {}",
+                self.byte_length(),
+                buffer.len()
+            );
+        }
+        let mut writer = BinaryWriter::new(buffer);
+        writer.write_u8(InputType::Custom as u8);
+        writer.write_string(&self.id);
+        writer.write_u16(self.fields.len() as u16);
+
+        for field in &self.fields {
+            writer.write_string(&field.id);
+            writer.write_u16(field.values.len() as u16);
+            for value in &field.values {
+                writer.write_i32(*value);
+            }
+        }
+    }
+
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut buffer = vec![0; self.byte_length()];
+        self.write_to(&mut buffer);
+        buffer
+    }
+
+    /// decodes `buffer` like `CustomInput::try_from`, but panics if it isn't a valid custom input.
+    pub fn from_bytes_unchecked(buffer: &[u8]) -> Self {
+        CustomInput::try_from(buffer).unwrap_or_else(|e| panic!("{}", e))
+    }
+}
+
+impl TryFrom<&[u8]> for CustomInput {
+    type Error = PogpError;
+
+    fn try_from(buffer: &[u8]) -> Result<Self, Self::Error> {
+        let mut reader = BinaryReader::new(buffer);
+        reader.read_input_type(InputType::Custom)?;
+        let id = reader.read_string()?;
+        let fields_length = reader.read_u16()? as usize;
+
+        let mut input = Self {
+            id,
+            fields: Vec::new(),
+        };
+
+        for _ in 0..fields_length {
+            let id = reader.read_string()?;
+            let values_length = reader.read_u16()? as usize;
+            let values = (0..values_length)
+                .map(|_| reader.read_i32())
+                .collect::<Result<_, _>>()?;
+            input.fields.push(CustomField { id, values });
+        }
+        Ok(input)
+    }
+}
+
+#[cfg(test)]
+mod custom_input {
+    use super::*;
+
+    #[test]
+    fn set_field() {
+        let mut input = CustomInput::new("my-flightstick");
+        input.set_field("whammybar", &[420, 69]).unwrap();
+        input.set_field("something", &[0]).unwrap();
+        input.set_field("whammybar", &[-1]).unwrap();
+
+        assert_eq!(input.id(), "my-flightstick");
+        assert_eq!(input.fields().len(), 2, "set field only adds once");
+        assert_eq!(input.field("whammybar"), Some(&[-1][..]));
+        assert_eq!(input.field("something"), Some(&[0][..]));
+        assert_eq!(input.field("nothing"), None);
+    }
+
+    #[test]
+    fn set_field_too_many_values() {
+        let mut input = CustomInput::new("pad");
+        assert_eq!(
+            input.set_field("samples", &vec![0; 65536]),
+            Err(PogpError::TooMany {
+                what: "values",
+                max: 65535,
+                actual: 65536
+            })
+        );
+        assert_eq!(input.field("samples"), None, "not set on error");
+        input.set_field("samples", &vec![0; 65535]).unwrap();
+    }
+
+    #[test]
+    fn from_slice_ref() {
+        let buffer: &[u8] = &[
+            0x05, 0x02, 0x00, 0x00, 0x00, b'd', b'j', 0x01, 0x00, 0x03, 0x00, 0x00, 0x00, b'f',
+            b'a', b'd', 0x02, 0x00, 0xa4, 0x01, 0x00, 0x00, 0xff, 0xff, 0xff, 0xff,
+        ];
+        let input = CustomInput::try_from(buffer).unwrap();
+        assert_eq!(input.id(), "dj");
+        assert_eq!(input.field("fad"), Some(&[420, -1][..]));
+    }
+
+    #[test]
+    fn round_trip() {
+        let mut input = CustomInput::new("dance-pad");
+        input.set_field("arrows", &[1, 0, 0, 1]).unwrap();
+        input.set_field("empty", &[]).unwrap();
+
+        let bytes = input.to_bytes();
+        assert_eq!(bytes.len(), input.byte_length());
+        assert_eq!(CustomInput::try_from(&bytes as &[u8]).unwrap(), input);
+    }
+
+    #[test]
+    fn try_from_bad_input_type() {
+        let buffer: [u8; 7] = [InputType::Gamepad as u8, 0, 0, 0, 0, 0, 0];
+        assert_eq!(
+            CustomInput::try_from(&buffer as &[u8]),
+            Err(PogpError::WrongInputType {
+                expected: InputType::Custom,
+                actual: 1
+            })
+        );
+    }
+
+    #[test]
+    fn try_from_truncated() {
+        let mut input = CustomInput::new("pad");
+        input.set_field("arrows", &[1, 0, 0, 1]).unwrap();
+        let bytes = input.to_bytes();
+        assert_eq!(
+            CustomInput::try_from(&bytes[..bytes.len() - 1]),
+            Err(PogpError::Truncated {
+                expected: bytes.len(),
+                actual: bytes.len() - 1
+            })
+        );
+    }
+}
@@ -0,0 +1,293 @@
+use super::binary_reader::BinaryReader;
+use super::binary_writer::BinaryWriter;
+use super::{ButtonPosition, Hand, InputType};
+use crate::PogpError;
+
+pub(super) const HEADER_LENGTH: usize = 1 + 2 + 2; // type, buttons.length, axes.length
+pub(super) const BUTTON_LENGTH: usize = 1 + 4; // position, value
+pub(super) const AXES_LENGTH: usize = 1 + 8 + 8; // hand, x, y
+
+/// stick values are percentages with four digits of precision, e.g. 10000 is 100%
+pub const STICK_MAX: i64 = 10000;
+/// stick values closer to the center than this read as centered, in the same scale as STICK_MAX
+pub const DEFAULT_DEAD_ZONE: i64 = 2000;
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct GamepadButton {
+    pub position: ButtonPosition,
+    /// percentage depressed with four digits of precision, e.g. 10000 is 100%
+    pub value: u32,
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct GamepadAxes {
+    pub hand: Hand,
+    pub x: i64,
+    pub y: i64,
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct GamepadInput {
+    buttons: Vec<GamepadButton>,
+    axes: Vec<GamepadAxes>,
+}
+
+impl GamepadInput {
+    pub fn new() -> Self {
+        Self {
+            buttons: Vec::new(),
+            axes: Vec::new(),
+        }
+    }
+
+    pub fn buttons(&self) -> &[GamepadButton] {
+        &self.buttons
+    }
+
+    pub fn axes(&self) -> &[GamepadAxes] {
+        &self.axes
+    }
+
+    pub fn set_button(&mut self, position: ButtonPosition, value: u32) {
+        match self.buttons.iter_mut().find(|b| b.position == position) {
+            Some(button) => button.value = value,
+            None => self.buttons.push(GamepadButton { position, value }),
+        }
+    }
+
+    pub fn set_axes(&mut self, hand: Hand, x: i64, y: i64) {
+        match self.axes.iter_mut().find(|a| a.hand == hand) {
+            Some(axes) => {
+                axes.x = x;
+                axes.y = y;
+            }
+            None => self.axes.push(GamepadAxes { hand, x, y }),
+        }
+    }
+
+    pub fn button(&self, position: ButtonPosition) -> Option<&GamepadButton> {
+        self.buttons.iter().find(|b| b.position == position)
+    }
+
+    pub fn axes_for(&self, hand: Hand) -> Option<&GamepadAxes> {
+        self.axes.iter().find(|a| a.hand == hand)
+    }
+
+    pub fn is_button_down(&self, position: ButtonPosition) -> bool {
+        self.button(position).map_or(false, |b| b.value > 0)
+    }
+
+    /// number of bytes this input occupies in the pogp binary format
+    pub fn byte_length(&self) -> usize {
+        HEADER_LENGTH + BUTTON_LENGTH * self.buttons.len() + AXES_LENGTH * self.axes.len()
+    }
+
+    /// writes this input in the pogp binary format to the start of `buffer`
+    pub fn write_to(&self, buffer: &mut [u8]) {
+        if buffer.len() < self.byte_length() {
+            panic!(
+                "Expected buffer of at least {} bytes, but it was {}",
+                self.byte_length(),
+                buffer.len()
+            );
+        }
+        let mut writer = BinaryWriter::new(buffer);
+        writer.write_u8(InputType::Gamepad as u8);
+        writer.write_u16(self.buttons.len() as u16);
+        writer.write_u16(self.axes.len() as u16);
+
+        for button in &self.buttons {
+            writer.write_u8(button.position.into());
+            writer.write_u32(button.value);
+        }
+
+        for axes in &self.axes {
+            writer.write_u8(axes.hand.into());
+            writer.write_i64(axes.x);
+            writer.write_i64(axes.y);
+        }
+    }
+
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut buffer = vec![0; self.byte_length()];
+        self.write_to(&mut buffer);
+        buffer
+    }
+
+    /// decodes `buffer` like `GamepadInput::try_from`, but panics if it isn't a valid gamepad input.
+    pub fn from_bytes_unchecked(buffer: &[u8]) -> Self {
+        GamepadInput::try_from(buffer).unwrap_or_else(|e| panic!("{}", e))
+    }
+}
+
+impl TryFrom<&[u8]> for GamepadInput {
+    type Error = PogpError;
+
+    /// buttons and axes with a position or hand this version doesn't know, e.g. from a newer
+    /// client, are skipped so the rest of the gamepad still reads
+    fn try_from(buffer: &[u8]) -> Result<Self, Self::Error> {
+        let mut reader = BinaryReader::new(buffer);
+        reader.read_input_type(InputType::Gamepad)?;
+        let buttons_length = reader.read_u16()? as usize;
+        let axes_length = reader.read_u16()? as usize;
+
+        // check the whole input fits before trusting the lengths
+        let byte_length =
+            HEADER_LENGTH + BUTTON_LENGTH * buttons_length + AXES_LENGTH * axes_length;
+        if buffer.len() < byte_length {
+            return Err(PogpError::Truncated {
+                expected: byte_length,
+                actual: buffer.len(),
+            });
+        }
+
+        let mut input = Self {
+            buttons: Vec::with_capacity(buttons_length),
+            axes: Vec::with_capacity(axes_length),
+        };
+
+        for _ in 0..buttons_length {
+            let position = ButtonPosition::try_from(reader.read_u8()?);
+            let value = reader.read_u32()?;
+            if let Ok(position) = position {
+                input.buttons.push(GamepadButton { position, value });
+            }
+        }
+
+        for _ in 0..axes_length {
+            let hand = Hand::try_from(reader.read_u8()?);
+            let x = reader.read_i64()?;
+            let y = reader.read_i64()?;
+            if let Ok(hand) = hand {
+                input.axes.push(GamepadAxes { hand, x, y });
+            }
+        }
+        Ok(input)
+    }
+}
+
+#[cfg(test)]
+mod gamepad_input {
+    use super::*;
+
+    #[test]
+    fn new() {
+        let input = GamepadInput::new();
+        assert_eq!(input.buttons().len(), 0);
+        assert_eq!(input.axes().len(), 0);
+    }
+
+    #[test]
+    fn set_button() {
+        let mut input = GamepadInput::new();
+        input.set_button(ButtonPosition::RightFaceBottom, 10000);
+        assert_eq!(input.is_button_down(ButtonPosition::RightFaceBottom), true);
+        input.set_button(ButtonPosition::RightFaceBottom, 0);
+        assert_eq!(input.buttons().len(), 1, "set button only adds once");
+        assert_eq!(input.is_button_down(ButtonPosition::RightFaceBottom), false);
+    }
+
+    #[test]
+    fn set_axes() {
+        let mut input = GamepadInput::new();
+        input.set_axes(Hand::Left, 0, 10000);
+        input.set_axes(Hand::Left, -10000, 0);
+        assert_eq!(input.axes().len(), 1, "set axes only adds once");
+        assert_eq!(
+            input.axes_for(Hand::Left),
+            Some(&GamepadAxes {
+                hand: Hand::Left,
+                x: -10000,
+                y: 0
+            })
+        );
+        assert_eq!(input.axes_for(Hand::Right), None);
+    }
+
+    #[test]
+    fn from_slice_ref() {
+        // matches the typescript MarshalInput.encodeGamepad output
+        let buffer: &[u8] = &[
+            0x01, 0x06, 0x00, 0x00, 0x00, 0x01, 0x00, 0x00, 0x00, 0x00, 0x02, 0x10, 0x27, 0x00,
+            0x00, 0x03, 0x00, 0x00, 0x00, 0x00, 0x04, 0x00, 0x00, 0x00, 0x00, 0x09, 0x10, 0x27,
+            0x00, 0x00, 0x0b, 0x10, 0x27, 0x00, 0x00,
+        ];
+        let input = GamepadInput::try_from(buffer).unwrap();
+        assert_eq!(input.buttons().len(), 6);
+        assert_eq!(input.axes().len(), 0);
+        assert_eq!(input.is_button_down(ButtonPosition::LeftFaceTop), false);
+        assert_eq!(input.is_button_down(ButtonPosition::LeftFaceRight), true);
+        assert_eq!(input.is_button_down(ButtonPosition::RightFaceRight), true);
+        assert_eq!(input.is_button_down(ButtonPosition::RightFaceLeft), true);
+        assert_eq!(
+            input.button(ButtonPosition::RightFaceLeft).unwrap().value,
+            10000
+        );
+    }
+
+    #[test]
+    fn round_trip() {
+        let mut input = GamepadInput::new();
+        input.set_button(ButtonPosition::Middle, 9500);
+        input.set_axes(Hand::Left, 50, -50);
+        input.set_axes(Hand::Right, i64::MAX, i64::MIN);
+
+        let bytes = input.to_bytes();
+        assert_eq!(bytes.len(), 5 + 5 + 17 * 2);
+        assert_eq!(GamepadInput::try_from(&bytes as &[u8]).unwrap(), input);
+    }
+
+    #[test]
+    fn try_from_bad_input_type() {
+        let buffer: [u8; 5] = [InputType::Keyboard as u8, 0, 0, 0, 0];
+        assert_eq!(
+            GamepadInput::try_from(&buffer as &[u8]),
+            Err(PogpError::WrongInputType {
+                expected: InputType::Gamepad,
+                actual: 4
+            })
+        );
+    }
+
+    #[test]
+    fn try_from_truncated() {
+        // claims one button but ends after the header
+        let buffer: [u8; 5] = [InputType::Gamepad as u8, 1, 0, 0, 0];
+        assert_eq!(
+            GamepadInput::try_from(&buffer as &[u8]),
+            Err(PogpError::Truncated {
+                expected: 10,
+                actual: 5
+            })
+        );
+        assert_eq!(
+            GamepadInput::try_from(&[] as &[u8]),
+            Err(PogpError::Truncated {
+                expected: 1,
+                actual: 0
+            })
+        );
+    }
+
+    #[test]
+    fn try_from_unknown_entries() {
+        let mut input = GamepadInput::new();
+        input.set_button(ButtonPosition::LeftFaceTop, 10000);
+        input.set_button(ButtonPosition::RightFaceBottom, 10000);
+        input.set_axes(Hand::Left, 1, 2);
+        input.set_axes(Hand::Right, 3, 4);
+        let mut bytes = input.to_bytes();
+        // a newer client's button position and hand
+        bytes[HEADER_LENGTH] = 42;
+        bytes[HEADER_LENGTH + 2 * BUTTON_LENGTH] = 7;
+
+        let input = GamepadInput::try_from(&bytes as &[u8]).unwrap();
+        assert_eq!(input.buttons().len(), 1, "unknown button skipped");
+        assert_eq!(input.is_button_down(ButtonPosition::RightFaceBottom), true);
+        assert_eq!(input.axes().len(), 1, "unknown hand skipped");
+        assert_eq!(
+            input.axes_for(Hand::Right).map(|a| (a.x, a.y)),
+            Some((3, 4))
+        );
+    }
+}
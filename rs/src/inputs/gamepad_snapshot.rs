@@ -0,0 +1,218 @@
+use super::GamepadAxes;
+use super::GamepadInput;
+use super::{ButtonPosition, Hand};
+
+const INPUT_BUFFER_LENGTH: usize = 10;
+
+/// GamepadSnapshot keeps the last `N` gamepad inputs to answer questions across frames.
+///
+/// Use `GamepadSnapshot::new()` for the default history of 10 frames, or
+/// `GamepadSnapshot::<N>::default()` to look further back. `N` must be at least 2.
+#[derive(Debug)]
+pub struct GamepadSnapshot<const N: usize = INPUT_BUFFER_LENGTH> {
+    inputs: [GamepadInput; N],
+    index: usize,
+}
+
+impl GamepadSnapshot {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl<const N: usize> GamepadSnapshot<N> {
+    pub fn add_input(&mut self, input: GamepadInput) {
+        self.inputs[self.index] = input;
+        self.advance_index();
+    }
+
+    pub fn is_button_down(&self, position: ButtonPosition) -> bool {
+        self.is_button(position, 0) && !self.is_button(position, 1)
+    }
+
+    pub fn is_button_pressed(&self, position: ButtonPosition) -> bool {
+        self.is_button(position, 0)
+    }
+
+    pub fn is_button_up(&self, position: ButtonPosition) -> bool {
+        !self.is_button(position, 0) && self.is_button(position, 1)
+    }
+
+    /// whether the button at `position` was held `frames_ago` frames before the latest input
+    pub fn is_button(&self, position: ButtonPosition, frames_ago: usize) -> bool {
+        if frames_ago >= N {
+            return false;
+        }
+        self.inputs[self.past_frame(frames_ago)].is_button_down(position)
+    }
+
+    pub fn axes(&self, hand: Hand) -> Option<&GamepadAxes> {
+        self.past_axes(hand, 0)
+    }
+
+    /// axes for `hand` as they were `frames_ago` frames before the latest input
+    pub fn past_axes(&self, hand: Hand, frames_ago: usize) -> Option<&GamepadAxes> {
+        if frames_ago >= N {
+            return None;
+        }
+        self.inputs[self.past_frame(frames_ago)].axes_for(hand)
+    }
+
+    fn advance_index(&mut self) {
+        self.index = if self.index == N - 1 {
+            0
+        } else {
+            self.index + 1
+        }
+    }
+
+    /// evaluated when a snapshot is created, so too few frames fail to compile
+    const ENOUGH_FRAMES: () = assert!(N >= 2, "GamepadSnapshot needs at least 2 frames");
+
+    fn past_frame(&self, frames_ago: usize) -> usize {
+        (self.index + N * 2 - 1 - frames_ago) % N
+    }
+}
+
+impl<const N: usize> Default for GamepadSnapshot<N> {
+    fn default() -> Self {
+        #[allow(clippy::let_unit_value)]
+        let () = Self::ENOUGH_FRAMES;
+        Self {
+            inputs: std::array::from_fn(|_| GamepadInput::new()),
+            index: 0,
+        }
+    }
+}
+
+#[cfg(test)]
+mod gamepad_snapshot {
+    use super::*;
+
+    fn pressed(position: ButtonPosition) -> GamepadInput {
+        let mut input = GamepadInput::new();
+        input.set_button(position, 10000);
+        input
+    }
+
+    #[test]
+    fn add_input() {
+        let mut snapshot = GamepadSnapshot::new();
+
+        for _ in 0..40 {
+            snapshot.add_input(pressed(ButtonPosition::RightFaceBottom));
+        }
+        assert_eq!(
+            snapshot.is_button_pressed(ButtonPosition::RightFaceBottom),
+            true
+        );
+    }
+
+    #[test]
+    fn is_button_down() {
+        let mut snapshot = GamepadSnapshot::new();
+        assert_eq!(
+            snapshot.is_button_down(ButtonPosition::RightFaceBottom),
+            false,
+            "false on empty"
+        );
+
+        snapshot.add_input(pressed(ButtonPosition::RightFaceBottom));
+        assert_eq!(
+            snapshot.is_button_down(ButtonPosition::RightFaceBottom),
+            true,
+            "true on button down"
+        );
+
+        snapshot.add_input(pressed(ButtonPosition::RightFaceBottom));
+        assert_eq!(
+            snapshot.is_button_down(ButtonPosition::RightFaceBottom),
+            false,
+            "false while button down"
+        );
+
+        snapshot.add_input(Default::default());
+        assert_eq!(
+            snapshot.is_button_down(ButtonPosition::RightFaceBottom),
+            false,
+            "false on button up"
+        );
+    }
+
+    #[test]
+    fn is_button_down_zero_value() {
+        let mut snapshot = GamepadSnapshot::new();
+        let mut input = GamepadInput::new();
+        input.set_button(ButtonPosition::RightFaceBottom, 0);
+        snapshot.add_input(input);
+        assert_eq!(
+            snapshot.is_button_down(ButtonPosition::RightFaceBottom),
+            false,
+            "false when button reports zero"
+        );
+    }
+
+    #[test]
+    fn is_button_up() {
+        let mut snapshot = GamepadSnapshot::new();
+        assert_eq!(
+            snapshot.is_button_up(ButtonPosition::RightFaceBottom),
+            false,
+            "false on empty"
+        );
+
+        snapshot.add_input(pressed(ButtonPosition::RightFaceBottom));
+        assert_eq!(
+            snapshot.is_button_up(ButtonPosition::RightFaceBottom),
+            false,
+            "false on button down"
+        );
+
+        snapshot.add_input(Default::default());
+        assert_eq!(
+            snapshot.is_button_up(ButtonPosition::RightFaceBottom),
+            true,
+            "true on button up"
+        );
+
+        snapshot.add_input(Default::default());
+        assert_eq!(
+            snapshot.is_button_up(ButtonPosition::RightFaceBottom),
+            false,
+            "false on next frame"
+        );
+    }
+
+    #[test]
+    fn axes() {
+        let mut snapshot = GamepadSnapshot::new();
+        assert_eq!(snapshot.axes(Hand::Left), None, "none on empty");
+
+        for x in 0..12 {
+            let mut input = GamepadInput::new();
+            input.set_axes(Hand::Left, x, 0);
+            snapshot.add_input(input);
+        }
+        assert_eq!(snapshot.axes(Hand::Left).unwrap().x, 11);
+        assert_eq!(snapshot.past_axes(Hand::Left, 1).unwrap().x, 10);
+        assert_eq!(snapshot.past_axes(Hand::Left, 9).unwrap().x, 2);
+        assert_eq!(
+            snapshot.past_axes(Hand::Left, 10),
+            None,
+            "none past the buffer length"
+        );
+    }
+
+    #[test]
+    fn longer_history() {
+        let mut snapshot = GamepadSnapshot::<20>::default();
+        snapshot.add_input(pressed(ButtonPosition::RightFaceBottom));
+        for _ in 0..15 {
+            snapshot.add_input(Default::default());
+        }
+        assert_eq!(
+            snapshot.is_button(ButtonPosition::RightFaceBottom, 15),
+            true
+        );
+    }
+}
@@ -0,0 +1,50 @@
+use crate::PogpError;
+
+/// The hand a gamepad thumbstick is intended to be used with.
+///
+/// Discriminants are part of the pogp binary format and match the typescript `Hand`.
+#[repr(u8)]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum Hand {
+    #[default]
+    Null = 0,
+    Left = 1,
+    Right = 2,
+}
+
+impl From<Hand> for u8 {
+    fn from(hand: Hand) -> Self {
+        hand as u8
+    }
+}
+
+impl TryFrom<u8> for Hand {
+    type Error = PogpError;
+
+    fn try_from(hand: u8) -> Result<Self, Self::Error> {
+        match hand {
+            0 => Ok(Hand::Null),
+            1 => Ok(Hand::Left),
+            2 => Ok(Hand::Right),
+            _ => Err(PogpError::UnknownHand(hand)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod hand {
+    use super::*;
+
+    #[test]
+    fn round_trip() {
+        for value in 0..=2 {
+            let hand = Hand::try_from(value).unwrap();
+            assert_eq!(u8::from(hand), value);
+        }
+    }
+
+    #[test]
+    fn try_from_unknown() {
+        assert_eq!(Hand::try_from(3), Err(PogpError::UnknownHand(3)));
+    }
+}
@@ -0,0 +1,231 @@
+use super::gamepad_input::{AXES_LENGTH, BUTTON_LENGTH, HEADER_LENGTH};
+use super::{GamepadInput, InputType, KeyboardInput, MouseInput, KEYBOARD_LENGTH, MOUSE_LENGTH};
+use crate::PogpError;
+
+pub const MAX_BUTTONS: usize = 20;
+pub const MAX_AXES: usize = 2;
+pub const MOUSE_OFFSET: usize = KEYBOARD_LENGTH;
+pub const GAMEPAD_OFFSET: usize = MOUSE_OFFSET + MOUSE_LENGTH;
+/// length of a frame with room for MAX_BUTTONS buttons and MAX_AXES axes
+pub const MAX_FRAME_LENGTH: usize =
+    GAMEPAD_OFFSET + HEADER_LENGTH + BUTTON_LENGTH * MAX_BUTTONS + AXES_LENGTH * MAX_AXES;
+
+/// InputFrame is every device's input for one frame, laid out in a shared buffer the same way as
+/// `MarshalInput` in the typescript client: keyboard at 0, mouse at MOUSE_OFFSET and gamepad at
+/// GAMEPAD_OFFSET.
+///
+/// A region whose type byte is Null, or that is past the end of the buffer, is a device that
+/// isn't connected.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct InputFrame {
+    pub keyboard: Option<KeyboardInput>,
+    pub mouse: Option<MouseInput>,
+    pub gamepad: Option<GamepadInput>,
+}
+
+impl InputFrame {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// number of bytes this frame occupies in the pogp binary format
+    pub fn byte_length(&self) -> usize {
+        GAMEPAD_OFFSET
+            + self
+                .gamepad
+                .as_ref()
+                .map_or(HEADER_LENGTH, |g| g.byte_length())
+    }
+
+    /// writes this frame in the pogp binary format to the start of `buffer`, regions for missing
+    /// devices are zeroed
+    pub fn write_to(&self, buffer: &mut [u8]) {
+        if buffer.len() < self.byte_length() {
+            panic!(
+                "Expected buffer of at least {} bytes, but it was {}",
+                self.byte_length(),
+                buffer.len()
+            );
+        }
+        let keyboard = &mut buffer[..MOUSE_OFFSET];
+        keyboard.fill(0);
+        if let Some(input) = &self.keyboard {
+            input.write_to(keyboard);
+        }
+
+        let mouse = &mut buffer[MOUSE_OFFSET..GAMEPAD_OFFSET];
+        mouse.fill(0);
+        if let Some(input) = &self.mouse {
+            input.write_to(mouse);
+        }
+
+        let gamepad = &mut buffer[GAMEPAD_OFFSET..];
+        match &self.gamepad {
+            Some(input) => input.write_to(gamepad),
+            None => gamepad[..HEADER_LENGTH].fill(0),
+        }
+    }
+
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut buffer = vec![0; self.byte_length()];
+        self.write_to(&mut buffer);
+        buffer
+    }
+}
+
+impl InputFrame {
+    /// decodes only the keyboard region of `buffer`, None if no keyboard is connected.
+    ///
+    /// Each device is decoded on its own, so a region a client sent wrong, or from a newer
+    /// version of pogp, doesn't lose the input of the other devices.
+    pub fn read_keyboard(buffer: &[u8]) -> Option<Result<KeyboardInput, PogpError>> {
+        region(buffer, 0).map(KeyboardInput::try_from)
+    }
+
+    /// decodes only the mouse region of `buffer`, see `read_keyboard`
+    pub fn read_mouse(buffer: &[u8]) -> Option<Result<MouseInput, PogpError>> {
+        region(buffer, MOUSE_OFFSET).map(MouseInput::try_from)
+    }
+
+    /// decodes only the gamepad region of `buffer`, see `read_keyboard`
+    pub fn read_gamepad(buffer: &[u8]) -> Option<Result<GamepadInput, PogpError>> {
+        region(buffer, GAMEPAD_OFFSET).map(GamepadInput::try_from)
+    }
+}
+
+impl TryFrom<&[u8]> for InputFrame {
+    type Error = PogpError;
+
+    /// errors if any device fails to decode, use `read_keyboard` and friends to keep the devices
+    /// that did
+    fn try_from(buffer: &[u8]) -> Result<Self, Self::Error> {
+        Ok(Self {
+            keyboard: Self::read_keyboard(buffer).transpose()?,
+            mouse: Self::read_mouse(buffer).transpose()?,
+            gamepad: Self::read_gamepad(buffer).transpose()?,
+        })
+    }
+}
+
+/// the rest of `buffer` from `offset`, if a device is present there
+fn region(buffer: &[u8], offset: usize) -> Option<&[u8]> {
+    buffer
+        .get(offset..)
+        .filter(|b| b.first().map_or(false, |t| *t != InputType::Null as u8))
+}
+
+#[cfg(test)]
+mod input_frame {
+    use super::*;
+    use crate::inputs::{ButtonPosition, Hand, Key};
+
+    #[test]
+    fn offsets() {
+        assert_eq!(MOUSE_OFFSET, 17);
+        assert_eq!(GAMEPAD_OFFSET, 27);
+        assert_eq!(MAX_FRAME_LENGTH, 166, "matches MarshalInput.byteLength()");
+    }
+
+    #[test]
+    fn from_slice_ref() {
+        let mut buffer = [0u8; MAX_FRAME_LENGTH];
+        // keyboard with ArrowDown
+        buffer[0] = 0x04;
+        buffer[1] = 0x02;
+        // mouse at 300, 5 clicked
+        buffer[17..27]
+            .copy_from_slice(&[0x03, 0x2c, 0x01, 0x00, 0x00, 0x05, 0x00, 0x00, 0x00, 0x01]);
+        // gamepad with one button
+        buffer[27..37]
+            .copy_from_slice(&[0x01, 0x01, 0x00, 0x00, 0x00, 0x0a, 0x10, 0x27, 0x00, 0x00]);
+
+        let frame = InputFrame::try_from(&buffer as &[u8]).unwrap();
+        assert_eq!(frame.keyboard.unwrap().is_key_down(Key::ArrowDown), true);
+        assert_eq!(frame.mouse, Some(MouseInput::new(300, 5, true)));
+        assert_eq!(
+            frame
+                .gamepad
+                .unwrap()
+                .is_button_down(ButtonPosition::RightFaceBottom),
+            true
+        );
+    }
+
+    #[test]
+    fn missing_devices() {
+        let keyboard = KeyboardInput::from(vec![Key::KeyA]).to_bytes();
+        let frame = InputFrame::try_from(&keyboard as &[u8]).unwrap();
+        assert_eq!(frame.keyboard.is_some(), true);
+        assert_eq!(frame.mouse, None, "none past the end of the buffer");
+        assert_eq!(frame.gamepad, None, "none past the end of the buffer");
+
+        let frame = InputFrame::try_from(&[0u8; MAX_FRAME_LENGTH] as &[u8]).unwrap();
+        assert_eq!(frame, InputFrame::new(), "none when regions are zeroed");
+    }
+
+    #[test]
+    fn round_trip() {
+        let mut gamepad = GamepadInput::new();
+        gamepad.set_button(ButtonPosition::LeftShoulderFront, 5000);
+        gamepad.set_axes(Hand::Right, -10000, 10000);
+        let frame = InputFrame {
+            keyboard: Some(KeyboardInput::from(vec![Key::Space, Key::KeyW])),
+            mouse: Some(MouseInput::new(1, 2, false)),
+            gamepad: Some(gamepad),
+        };
+
+        let mut buffer = [0xff; MAX_FRAME_LENGTH];
+        frame.write_to(&mut buffer);
+        assert_eq!(InputFrame::try_from(&buffer as &[u8]).unwrap(), frame);
+
+        let frame = InputFrame::new();
+        frame.write_to(&mut buffer);
+        assert_eq!(
+            InputFrame::try_from(&buffer as &[u8]).unwrap(),
+            frame,
+            "missing devices overwrite stale regions"
+        );
+    }
+
+    #[test]
+    fn try_from_wrong_region() {
+        let mut buffer = [0u8; MAX_FRAME_LENGTH];
+        buffer[MOUSE_OFFSET] = InputType::Gamepad as u8;
+        assert_eq!(
+            InputFrame::try_from(&buffer as &[u8]),
+            Err(PogpError::WrongInputType {
+                expected: InputType::Mouse,
+                actual: 1
+            })
+        );
+    }
+
+    #[test]
+    fn read_devices_separately() {
+        let frame = InputFrame {
+            keyboard: Some(KeyboardInput::from(vec![Key::ArrowUp])),
+            ..Default::default()
+        };
+        let mut buffer = [0u8; MAX_FRAME_LENGTH];
+        frame.write_to(&mut buffer);
+        // a gamepad claiming more buttons than the buffer holds
+        buffer[GAMEPAD_OFFSET] = InputType::Gamepad as u8;
+        buffer[GAMEPAD_OFFSET + 1] = 0xff;
+
+        assert_eq!(
+            InputFrame::try_from(&buffer as &[u8]).is_err(),
+            true,
+            "all or nothing"
+        );
+        assert_eq!(
+            InputFrame::read_keyboard(&buffer),
+            Some(Ok(KeyboardInput::from(vec![Key::ArrowUp]))),
+            "keyboard survives a corrupt gamepad"
+        );
+        assert_eq!(InputFrame::read_mouse(&buffer), None);
+        assert_eq!(
+            InputFrame::read_gamepad(&buffer).map(|g| g.is_err()),
+            Some(true)
+        );
+    }
+}
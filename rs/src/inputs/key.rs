@@ -1,5 +1,19 @@
+use std::fmt;
+use std::str::FromStr;
+
+use super::KEYBOARD_KEY_COUNT;
+use crate::PogpError;
+
+/// Key is a physical key, named after the w3c UIEvents `code` it produces.
+///
+/// Values are stable across versions and index into the 128 bit keyboard field, so the enum can't
+/// grow past 127.
+///
+/// With only 127 slots some UIEvents codes are left out, and decode as no key: F16 to F24, which
+/// only extended keyboards have, plus Fn and FnLock, which browsers don't report, and the
+/// browser, launch, power and legacy editing keys.
 #[repr(u8)]
-#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
 pub enum Key {
     Null,
 
@@ -73,6 +87,358 @@ pub enum Key {
     Period,
     Slash,
     IntlRo,
+
+    Escape,
+    Delete,
+    Insert,
+    Home,
+    End,
+    PageUp,
+    PageDown,
+
+    F1,
+    F2,
+    F3,
+    F4,
+    F5,
+    F6,
+    F7,
+    F8,
+    F9,
+    F10,
+    F11,
+    F12,
+    F13,
+    F14,
+    F15,
+
+    // IME and international keys of Korean, Japanese and Brazilian keyboards
+    Lang1,
+    Lang2,
+    Lang3,
+    Lang4,
+    Lang5,
+    Convert,
+    NonConvert,
+    KanaMode,
+    NumpadComma,
+
+    NumLock,
+    Numpad0,
+    Numpad1,
+    Numpad2,
+    Numpad3,
+    Numpad4,
+    Numpad5,
+    Numpad6,
+    Numpad7,
+    Numpad8,
+    Numpad9,
+    NumpadAdd,
+    NumpadSubtract,
+    NumpadMultiply,
+    NumpadDivide,
+    NumpadDecimal,
+    NumpadEnter,
+    NumpadEqual,
+
+    PrintScreen,
+    ScrollLock,
+    Pause,
+
+    MediaPlayPause,
+    MediaStop,
+    MediaTrackNext,
+    MediaTrackPrevious,
+    AudioVolumeUp,
+    AudioVolumeDown,
+    AudioVolumeMute,
+}
+
+impl Key {
+    /// every key except Null, in id order
+    pub const ALL: [Key; KEYBOARD_KEY_COUNT - 1] = [
+        Key::ArrowDown,
+        Key::ArrowLeft,
+        Key::ArrowRight,
+        Key::ArrowUp,
+        Key::Backspace,
+        Key::Tab,
+        Key::CapsLock,
+        Key::Enter,
+        Key::ShiftLeft,
+        Key::ShiftRight,
+        Key::ControlLeft,
+        Key::MetaLeft,
+        Key::AltLeft,
+        Key::Space,
+        Key::AltRight,
+        Key::MetaRight,
+        Key::ContextMenu,
+        Key::ControlRight,
+        Key::Backquote,
+        Key::Digit1,
+        Key::Digit2,
+        Key::Digit3,
+        Key::Digit4,
+        Key::Digit5,
+        Key::Digit6,
+        Key::Digit7,
+        Key::Digit8,
+        Key::Digit9,
+        Key::Digit0,
+        Key::Minus,
+        Key::Equal,
+        Key::IntlYen,
+        Key::KeyQ,
+        Key::KeyW,
+        Key::KeyE,
+        Key::KeyR,
+        Key::KeyT,
+        Key::KeyY,
+        Key::KeyU,
+        Key::KeyI,
+        Key::KeyO,
+        Key::KeyP,
+        Key::BracketLeft,
+        Key::BracketRight,
+        Key::Backslash,
+        Key::KeyA,
+        Key::KeyS,
+        Key::KeyD,
+        Key::KeyF,
+        Key::KeyG,
+        Key::KeyH,
+        Key::KeyJ,
+        Key::KeyK,
+        Key::KeyL,
+        Key::Semicolon,
+        Key::Quote,
+        Key::IntlBackslash,
+        Key::KeyZ,
+        Key::KeyX,
+        Key::KeyC,
+        Key::KeyV,
+        Key::KeyB,
+        Key::KeyN,
+        Key::KeyM,
+        Key::Comma,
+        Key::Period,
+        Key::Slash,
+        Key::IntlRo,
+        Key::Escape,
+        Key::Delete,
+        Key::Insert,
+        Key::Home,
+        Key::End,
+        Key::PageUp,
+        Key::PageDown,
+        Key::F1,
+        Key::F2,
+        Key::F3,
+        Key::F4,
+        Key::F5,
+        Key::F6,
+        Key::F7,
+        Key::F8,
+        Key::F9,
+        Key::F10,
+        Key::F11,
+        Key::F12,
+        Key::F13,
+        Key::F14,
+        Key::F15,
+        Key::Lang1,
+        Key::Lang2,
+        Key::Lang3,
+        Key::Lang4,
+        Key::Lang5,
+        Key::Convert,
+        Key::NonConvert,
+        Key::KanaMode,
+        Key::NumpadComma,
+        Key::NumLock,
+        Key::Numpad0,
+        Key::Numpad1,
+        Key::Numpad2,
+        Key::Numpad3,
+        Key::Numpad4,
+        Key::Numpad5,
+        Key::Numpad6,
+        Key::Numpad7,
+        Key::Numpad8,
+        Key::Numpad9,
+        Key::NumpadAdd,
+        Key::NumpadSubtract,
+        Key::NumpadMultiply,
+        Key::NumpadDivide,
+        Key::NumpadDecimal,
+        Key::NumpadEnter,
+        Key::NumpadEqual,
+        Key::PrintScreen,
+        Key::ScrollLock,
+        Key::Pause,
+        Key::MediaPlayPause,
+        Key::MediaStop,
+        Key::MediaTrackNext,
+        Key::MediaTrackPrevious,
+        Key::AudioVolumeUp,
+        Key::AudioVolumeDown,
+        Key::AudioVolumeMute,
+    ];
+
+    /// the key for a w3c UIEvents `code`, e.g. "KeyA" or "ArrowUp"
+    pub fn from_code(code: &str) -> Option<Key> {
+        Key::ALL.iter().copied().find(|key| key.code() == code)
+    }
+
+    /// the w3c UIEvents `code` for this key, Null is the empty string like an unidentified key
+    pub fn code(&self) -> &'static str {
+        match self {
+            Key::Null => "",
+            Key::ArrowDown => "ArrowDown",
+            Key::ArrowLeft => "ArrowLeft",
+            Key::ArrowRight => "ArrowRight",
+            Key::ArrowUp => "ArrowUp",
+            Key::Backspace => "Backspace",
+            Key::Tab => "Tab",
+            Key::CapsLock => "CapsLock",
+            Key::Enter => "Enter",
+            Key::ShiftLeft => "ShiftLeft",
+            Key::ShiftRight => "ShiftRight",
+            Key::ControlLeft => "ControlLeft",
+            Key::MetaLeft => "MetaLeft",
+            Key::AltLeft => "AltLeft",
+            Key::Space => "Space",
+            Key::AltRight => "AltRight",
+            Key::MetaRight => "MetaRight",
+            Key::ContextMenu => "ContextMenu",
+            Key::ControlRight => "ControlRight",
+            Key::Backquote => "Backquote",
+            Key::Digit1 => "Digit1",
+            Key::Digit2 => "Digit2",
+            Key::Digit3 => "Digit3",
+            Key::Digit4 => "Digit4",
+            Key::Digit5 => "Digit5",
+            Key::Digit6 => "Digit6",
+            Key::Digit7 => "Digit7",
+            Key::Digit8 => "Digit8",
+            Key::Digit9 => "Digit9",
+            Key::Digit0 => "Digit0",
+            Key::Minus => "Minus",
+            Key::Equal => "Equal",
+            Key::IntlYen => "IntlYen",
+            Key::KeyQ => "KeyQ",
+            Key::KeyW => "KeyW",
+            Key::KeyE => "KeyE",
+            Key::KeyR => "KeyR",
+            Key::KeyT => "KeyT",
+            Key::KeyY => "KeyY",
+            Key::KeyU => "KeyU",
+            Key::KeyI => "KeyI",
+            Key::KeyO => "KeyO",
+            Key::KeyP => "KeyP",
+            Key::BracketLeft => "BracketLeft",
+            Key::BracketRight => "BracketRight",
+            Key::Backslash => "Backslash",
+            Key::KeyA => "KeyA",
+            Key::KeyS => "KeyS",
+            Key::KeyD => "KeyD",
+            Key::KeyF => "KeyF",
+            Key::KeyG => "KeyG",
+            Key::KeyH => "KeyH",
+            Key::KeyJ => "KeyJ",
+            Key::KeyK => "KeyK",
+            Key::KeyL => "KeyL",
+            Key::Semicolon => "Semicolon",
+            Key::Quote => "Quote",
+            Key::IntlBackslash => "IntlBackslash",
+            Key::KeyZ => "KeyZ",
+            Key::KeyX => "KeyX",
+            Key::KeyC => "KeyC",
+            Key::KeyV => "KeyV",
+            Key::KeyB => "KeyB",
+            Key::KeyN => "KeyN",
+            Key::KeyM => "KeyM",
+            Key::Comma => "Comma",
+            Key::Period => "Period",
+            Key::Slash => "Slash",
+            Key::IntlRo => "IntlRo",
+            Key::Escape => "Escape",
+            Key::Delete => "Delete",
+            Key::Insert => "Insert",
+            Key::Home => "Home",
+            Key::End => "End",
+            Key::PageUp => "PageUp",
+            Key::PageDown => "PageDown",
+            Key::F1 => "F1",
+            Key::F2 => "F2",
+            Key::F3 => "F3",
+            Key::F4 => "F4",
+            Key::F5 => "F5",
+            Key::F6 => "F6",
+            Key::F7 => "F7",
+            Key::F8 => "F8",
+            Key::F9 => "F9",
+            Key::F10 => "F10",
+            Key::F11 => "F11",
+            Key::F12 => "F12",
+            Key::F13 => "F13",
+            Key::F14 => "F14",
+            Key::F15 => "F15",
+            Key::Lang1 => "Lang1",
+            Key::Lang2 => "Lang2",
+            Key::Lang3 => "Lang3",
+            Key::Lang4 => "Lang4",
+            Key::Lang5 => "Lang5",
+            Key::Convert => "Convert",
+            Key::NonConvert => "NonConvert",
+            Key::KanaMode => "KanaMode",
+            Key::NumpadComma => "NumpadComma",
+            Key::NumLock => "NumLock",
+            Key::Numpad0 => "Numpad0",
+            Key::Numpad1 => "Numpad1",
+            Key::Numpad2 => "Numpad2",
+            Key::Numpad3 => "Numpad3",
+            Key::Numpad4 => "Numpad4",
+            Key::Numpad5 => "Numpad5",
+            Key::Numpad6 => "Numpad6",
+            Key::Numpad7 => "Numpad7",
+            Key::Numpad8 => "Numpad8",
+            Key::Numpad9 => "Numpad9",
+            Key::NumpadAdd => "NumpadAdd",
+            Key::NumpadSubtract => "NumpadSubtract",
+            Key::NumpadMultiply => "NumpadMultiply",
+            Key::NumpadDivide => "NumpadDivide",
+            Key::NumpadDecimal => "NumpadDecimal",
+            Key::NumpadEnter => "NumpadEnter",
+            Key::NumpadEqual => "NumpadEqual",
+            Key::PrintScreen => "PrintScreen",
+            Key::ScrollLock => "ScrollLock",
+            Key::Pause => "Pause",
+            Key::MediaPlayPause => "MediaPlayPause",
+            Key::MediaStop => "MediaStop",
+            Key::MediaTrackNext => "MediaTrackNext",
+            Key::MediaTrackPrevious => "MediaTrackPrevious",
+            Key::AudioVolumeUp => "AudioVolumeUp",
+            Key::AudioVolumeDown => "AudioVolumeDown",
+            Key::AudioVolumeMute => "AudioVolumeMute",
+        }
+    }
+}
+
+impl FromStr for Key {
+    type Err = PogpError;
+
+    fn from_str(code: &str) -> Result<Self, Self::Err> {
+        Key::from_code(code).ok_or_else(|| PogpError::UnknownKeyCode(code.to_string()))
+    }
+}
+
+impl fmt::Display for Key {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.code())
+    }
 }
 
 impl From<u8> for Key {
@@ -149,7 +515,130 @@ impl From<u8> for Key {
             67 => Key::Slash,
             68 => Key::IntlRo,
 
+            69 => Key::Escape,
+            70 => Key::Delete,
+            71 => Key::Insert,
+            72 => Key::Home,
+            73 => Key::End,
+            74 => Key::PageUp,
+            75 => Key::PageDown,
+
+            76 => Key::F1,
+            77 => Key::F2,
+            78 => Key::F3,
+            79 => Key::F4,
+            80 => Key::F5,
+            81 => Key::F6,
+            82 => Key::F7,
+            83 => Key::F8,
+            84 => Key::F9,
+            85 => Key::F10,
+            86 => Key::F11,
+            87 => Key::F12,
+            88 => Key::F13,
+            89 => Key::F14,
+            90 => Key::F15,
+            91 => Key::Lang1,
+            92 => Key::Lang2,
+            93 => Key::Lang3,
+            94 => Key::Lang4,
+            95 => Key::Lang5,
+            96 => Key::Convert,
+            97 => Key::NonConvert,
+            98 => Key::KanaMode,
+            99 => Key::NumpadComma,
+
+            100 => Key::NumLock,
+            101 => Key::Numpad0,
+            102 => Key::Numpad1,
+            103 => Key::Numpad2,
+            104 => Key::Numpad3,
+            105 => Key::Numpad4,
+            106 => Key::Numpad5,
+            107 => Key::Numpad6,
+            108 => Key::Numpad7,
+            109 => Key::Numpad8,
+            110 => Key::Numpad9,
+            111 => Key::NumpadAdd,
+            112 => Key::NumpadSubtract,
+            113 => Key::NumpadMultiply,
+            114 => Key::NumpadDivide,
+            115 => Key::NumpadDecimal,
+            116 => Key::NumpadEnter,
+            117 => Key::NumpadEqual,
+
+            118 => Key::PrintScreen,
+            119 => Key::ScrollLock,
+            120 => Key::Pause,
+
+            121 => Key::MediaPlayPause,
+            122 => Key::MediaStop,
+            123 => Key::MediaTrackNext,
+            124 => Key::MediaTrackPrevious,
+            125 => Key::AudioVolumeUp,
+            126 => Key::AudioVolumeDown,
+            127 => Key::AudioVolumeMute,
+
             _ => Key::Null,
         }
     }
 }
+
+#[cfg(test)]
+mod key {
+    use super::*;
+
+    #[test]
+    fn from_u8() {
+        assert_eq!(Key::from(0), Key::Null);
+        assert_eq!(Key::from(68), Key::IntlRo);
+        assert_eq!(Key::from(69), Key::Escape);
+        assert_eq!(Key::from(127), Key::AudioVolumeMute);
+        assert_eq!(Key::from(128), Key::Null, "past the keyboard field");
+    }
+
+    #[test]
+    fn round_trip() {
+        for id in 0..KEYBOARD_KEY_COUNT as u8 {
+            assert_eq!(
+                Key::from(id) as u8,
+                id,
+                "every id in the keyboard field is a key"
+            );
+        }
+    }
+
+    #[test]
+    fn code() {
+        assert_eq!(Key::KeyA.code(), "KeyA");
+        assert_eq!(Key::ArrowUp.to_string(), "ArrowUp");
+        assert_eq!(Key::Null.code(), "");
+    }
+
+    #[test]
+    fn from_code() {
+        assert_eq!(Key::from_code("NumpadEnter"), Some(Key::NumpadEnter));
+        assert_eq!(Key::from_code("keya"), None, "codes are case sensitive");
+        assert_eq!(Key::from_code(""), None, "Null has no code");
+        assert_eq!("F12".parse::<Key>(), Ok(Key::F12));
+        assert_eq!(
+            "Fn".parse::<Key>(),
+            Err(PogpError::UnknownKeyCode("Fn".to_string()))
+        );
+        assert_eq!("Lang1".parse::<Key>(), Ok(Key::Lang1));
+        assert_eq!("NumpadComma".parse::<Key>(), Ok(Key::NumpadComma));
+        assert_eq!(
+            "F16".parse::<Key>(),
+            Err(PogpError::UnknownKeyCode("F16".to_string())),
+            "F16 to F24 are left out"
+        );
+    }
+
+    #[test]
+    fn all() {
+        assert_eq!(Key::ALL[0], Key::ArrowDown);
+        for key in Key::ALL {
+            assert_eq!(Key::from_code(key.code()), Some(key), "{:?}", key);
+        }
+    }
+}
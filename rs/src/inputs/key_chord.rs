@@ -0,0 +1,155 @@
+use std::fmt;
+use std::str::FromStr;
+
+use super::{Key, KeyboardInput, Modifiers};
+use crate::PogpError;
+
+/// KeyChord is a key pressed with an exact set of modifiers, e.g. Ctrl+Shift+S.
+///
+/// Chords match physical keys, so "Ctrl+Z" is the key in the Z position on a US keyboard whatever
+/// the player's layout.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct KeyChord {
+    pub modifiers: Modifiers,
+    pub key: Key,
+}
+
+impl KeyChord {
+    pub fn new(modifiers: Modifiers, key: Key) -> Self {
+        Self { modifiers, key }
+    }
+
+    /// whether `key` is down with exactly these modifiers, so Ctrl+S doesn't match Ctrl+Shift+S
+    pub fn is_held(&self, input: &KeyboardInput) -> bool {
+        input.is_key_down(self.key) && Modifiers::from(input) == self.modifiers
+    }
+}
+
+impl FromStr for KeyChord {
+    type Err = PogpError;
+
+    /// parses modifiers and a key joined by +, e.g. "Ctrl+Shift+S", "Alt+F4" or "Meta+Digit1".
+    /// Letters and digits can be written as themselves, anything else as its w3c code.
+    fn from_str(chord: &str) -> Result<Self, Self::Err> {
+        let invalid = || PogpError::InvalidChord(chord.to_string());
+        let mut parts = chord.split('+').map(str::trim);
+        let key = parts.next_back().and_then(parse_key).ok_or_else(invalid)?;
+
+        let mut modifiers = Modifiers::NONE;
+        for part in parts {
+            modifiers |= parse_modifier(part).ok_or_else(invalid)?;
+        }
+        Ok(Self { modifiers, key })
+    }
+}
+
+impl fmt::Display for KeyChord {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (modifier, name) in [
+            (Modifiers::CTRL, "Ctrl"),
+            (Modifiers::ALT, "Alt"),
+            (Modifiers::SHIFT, "Shift"),
+            (Modifiers::META, "Meta"),
+        ] {
+            if self.modifiers.contains(modifier) {
+                write!(f, "{}+", name)?;
+            }
+        }
+        let code = self.key.code();
+        let short = code
+            .strip_prefix("Key")
+            .or_else(|| code.strip_prefix("Digit"));
+        f.write_str(short.unwrap_or(code))
+    }
+}
+
+fn parse_modifier(name: &str) -> Option<Modifiers> {
+    match name.to_ascii_lowercase().as_str() {
+        "ctrl" | "control" => Some(Modifiers::CTRL),
+        "alt" | "option" => Some(Modifiers::ALT),
+        "shift" => Some(Modifiers::SHIFT),
+        "meta" | "cmd" | "command" | "super" | "win" => Some(Modifiers::META),
+        _ => None,
+    }
+}
+
+fn parse_key(name: &str) -> Option<Key> {
+    let mut chars = name.chars();
+    let key = match (chars.next(), chars.next()) {
+        (Some(c), None) if c.is_ascii_alphabetic() => {
+            Key::from_code(&format!("Key{}", c.to_ascii_uppercase()))
+        }
+        (Some(c), None) if c.is_ascii_digit() => Key::from_code(&format!("Digit{}", c)),
+        _ => Key::from_code(name),
+    }?;
+    // a chord needs a key to press besides its modifiers
+    match Modifiers::from_key(key) {
+        Some(_) => None,
+        None => Some(key),
+    }
+}
+
+#[cfg(test)]
+mod key_chord {
+    use super::*;
+
+    #[test]
+    fn from_str() {
+        assert_eq!(
+            "Ctrl+Shift+S".parse(),
+            Ok(KeyChord::new(Modifiers::CTRL | Modifiers::SHIFT, Key::KeyS))
+        );
+        assert_eq!(
+            "alt + F4".parse(),
+            Ok(KeyChord::new(Modifiers::ALT, Key::F4))
+        );
+        assert_eq!("1".parse(), Ok(KeyChord::new(Modifiers::NONE, Key::Digit1)));
+        assert_eq!(
+            "Cmd+BracketLeft".parse(),
+            Ok(KeyChord::new(Modifiers::META, Key::BracketLeft))
+        );
+    }
+
+    #[test]
+    fn from_str_invalid() {
+        for chord in [
+            "",
+            "Ctrl+",
+            "Hyper+S",
+            "Ctrl+Shift",
+            "Ctrl+ShiftLeft",
+            "S+Ctrl",
+        ] {
+            assert_eq!(
+                chord.parse::<KeyChord>(),
+                Err(PogpError::InvalidChord(chord.to_string())),
+                "{}",
+                chord
+            );
+        }
+    }
+
+    #[test]
+    fn display() {
+        let chord: KeyChord = "shift+ctrl+s".parse().unwrap();
+        assert_eq!(chord.to_string(), "Ctrl+Shift+S");
+        assert_eq!(
+            KeyChord::new(Modifiers::NONE, Key::Escape).to_string(),
+            "Escape"
+        );
+    }
+
+    #[test]
+    fn is_held() {
+        let chord: KeyChord = "Ctrl+S".parse().unwrap();
+        let held = |keys: Vec<Key>| chord.is_held(&KeyboardInput::from(keys));
+        assert_eq!(held(vec![Key::ControlLeft, Key::KeyS]), true);
+        assert_eq!(held(vec![Key::ControlRight, Key::KeyS]), true);
+        assert_eq!(held(vec![Key::KeyS]), false, "missing modifier");
+        assert_eq!(
+            held(vec![Key::ControlLeft, Key::ShiftLeft, Key::KeyS]),
+            false,
+            "extra modifier"
+        );
+    }
+}
@@ -0,0 +1,96 @@
+use super::{Key, KeyboardSnapshot};
+
+/// KeyRepeat turns a held key into repeated presses, like holding an arrow key in a text box.
+///
+/// It fires on the frame a key goes down, again `delay` frames later, then every `rate` frames
+/// while the key is held. It's counted in frames and keeps no state of its own, so repeats land
+/// on the same frames on every host.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct KeyRepeat {
+    /// frames from the press to the first repeat
+    pub delay: u32,
+    /// frames between repeats after the first, 0 to only repeat once
+    pub rate: u32,
+}
+
+impl KeyRepeat {
+    pub fn new(delay: u32, rate: u32) -> Self {
+        Self { delay, rate }
+    }
+
+    /// whether a key that's been held for `held_frames` frames fires this frame
+    pub fn fires(&self, held_frames: u32) -> bool {
+        match held_frames {
+            0 => false,
+            1 => true,
+            held if held <= self.delay => false,
+            held if held - 1 == self.delay => true,
+            // a 0 rate never repeats again, as the first repeat was handled above
+            held => self.rate != 0 && (held - 1 - self.delay) % self.rate == 0,
+        }
+    }
+
+    /// whether `key` is pressed or repeats this frame
+    pub fn is_key_repeated<const N: usize>(
+        &self,
+        snapshot: &KeyboardSnapshot<N>,
+        key: Key,
+    ) -> bool {
+        self.fires(snapshot.held_frames(key))
+    }
+}
+
+impl Default for KeyRepeat {
+    /// half a second delay and ten repeats a second at 60fps
+    fn default() -> Self {
+        Self::new(30, 6)
+    }
+}
+
+#[cfg(test)]
+mod key_repeat {
+    use super::*;
+    use crate::inputs::KeyboardInput;
+
+    #[test]
+    fn fires() {
+        let repeat = KeyRepeat::new(3, 2);
+        let frames: Vec<u32> = (0..12).filter(|held| repeat.fires(*held)).collect();
+        assert_eq!(frames, vec![1, 4, 6, 8, 10]);
+    }
+
+    #[test]
+    fn fires_zero_rate() {
+        let repeat = KeyRepeat::new(2, 0);
+        let frames: Vec<u32> = (0..12).filter(|held| repeat.fires(*held)).collect();
+        assert_eq!(frames, vec![1, 3], "repeats once");
+    }
+
+    #[test]
+    fn is_key_repeated() {
+        let repeat = KeyRepeat::new(4, 1);
+        let mut snapshot = KeyboardSnapshot::new();
+        let mut repeats = Vec::new();
+        for frame in 0..8 {
+            snapshot.add_input(KeyboardInput::from(vec![Key::ArrowDown]));
+            repeats.push(repeat.is_key_repeated(&snapshot, Key::ArrowDown));
+            assert_eq!(
+                repeat.is_key_repeated(&snapshot, Key::ArrowUp),
+                false,
+                "frame {}",
+                frame
+            );
+        }
+        assert_eq!(
+            repeats,
+            vec![true, false, false, false, true, true, true, true]
+        );
+
+        snapshot.add_input(Default::default());
+        assert_eq!(
+            repeat.is_key_repeated(&snapshot, Key::ArrowDown),
+            false,
+            "false on release"
+        );
+    }
+}
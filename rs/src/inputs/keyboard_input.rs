@@ -1,66 +1,189 @@
-use super::{InputType, Key};
+use super::{InputType, Key, Modifiers};
+use crate::PogpError;
 
-#[derive(Debug, Default)]
+/// number of keys the keyboard binary format has room for
+pub const KEYBOARD_KEY_COUNT: usize = 128;
+/// bytes in the keyboard binary format, matching `KEYBOARD_LENGTH` in the typescript client
+pub const KEYBOARD_LENGTH: usize = 1 + KEYBOARD_KEY_COUNT / 8; // type, 128 key bools
+
+/// KeyboardInput is the set of keys held down in a frame.
+///
+/// Keys are stored as a bitset indexed by `Key` discriminant, so inputs are `Copy`, lookups are
+/// O(1) and decoding never allocates.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
 pub struct KeyboardInput {
-    keys: Vec<Key>,
+    bits: [u64; KEYBOARD_KEY_COUNT / 64],
 }
 
 impl KeyboardInput {
-    pub fn new() -> Self {
-        Self { keys: Vec::new() }
+    pub const fn new() -> Self {
+        Self {
+            bits: [0; KEYBOARD_KEY_COUNT / 64],
+        }
     }
 
+    /// Null isn't a key, setting it does nothing
     pub fn set_key(&mut self, key: Key) {
-        if self.is_key_down(key) {
+        if key == Key::Null {
             return;
         }
-        self.keys.push(key);
+        let bit = key as usize;
+        self.bits[bit / 64] |= 1 << (bit % 64);
     }
 
     pub fn is_key_down(&self, key: Key) -> bool {
-        return self.keys.contains(&key);
+        let bit = key as usize;
+        self.bits[bit / 64] & (1 << (bit % 64)) > 0
+    }
+
+    /// number of keys held down
+    pub fn len(&self) -> usize {
+        self.bits.iter().map(|b| b.count_ones() as usize).sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.bits.iter().all(|b| *b == 0)
+    }
+
+    pub fn modifiers(&self) -> Modifiers {
+        Modifiers::from(self)
+    }
+
+    /// keys held down, in id order
+    pub fn keys(&self) -> Keys {
+        Keys {
+            bits: self.bits,
+            word: 0,
+        }
+    }
+
+    /// keys that changed from `previous` to this input
+    pub fn diff(&self, previous: &KeyboardInput) -> KeyboardDiff {
+        let mut diff = KeyboardDiff::default();
+        for (word, (now, then)) in self.bits.iter().zip(previous.bits).enumerate() {
+            diff.pressed.bits[word] = now & !then;
+            diff.released.bits[word] = then & !now;
+        }
+        diff
+    }
+
+    /// writes this input in the pogp binary format to the start of `buffer`
+    pub fn write_to(&self, buffer: &mut [u8]) {
+        if buffer.len() < KEYBOARD_LENGTH {
+            panic!(
+                "Expected buffer of at least {} bytes, but it was {}",
+                KEYBOARD_LENGTH,
+                buffer.len()
+            );
+        }
+        buffer[0] = InputType::Keyboard as u8;
+        for (byte_index, byte) in buffer[1..KEYBOARD_LENGTH].iter_mut().enumerate() {
+            let bit = byte_index * 8;
+            *byte = (self.bits[bit / 64] >> (bit % 64)) as u8;
+        }
+    }
+
+    pub fn to_bytes(&self) -> [u8; KEYBOARD_LENGTH] {
+        let mut buffer = [0; KEYBOARD_LENGTH];
+        self.write_to(&mut buffer);
+        buffer
+    }
+
+    /// decodes `buffer` like `KeyboardInput::try_from`, but panics if it isn't a valid keyboard input.
+    /// Replaces the panicking `From<&[u8]>` of earlier versions, which can't coexist with
+    /// `TryFrom<&[u8]>`.
+    pub fn from_bytes_unchecked(buffer: &[u8]) -> Self {
+        KeyboardInput::try_from(buffer).unwrap_or_else(|e| panic!("{}", e))
+    }
+}
+
+/// KeyboardDiff is the keys that went down and came up between two inputs
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct KeyboardDiff {
+    pub pressed: KeyboardInput,
+    pub released: KeyboardInput,
+}
+
+/// iterator over the keys held in a KeyboardInput, see `KeyboardInput::keys`
+#[derive(Clone, Debug)]
+pub struct Keys {
+    bits: [u64; KEYBOARD_KEY_COUNT / 64],
+    word: usize,
+}
+
+impl Iterator for Keys {
+    type Item = Key;
+
+    fn next(&mut self) -> Option<Key> {
+        while self.word < self.bits.len() {
+            let bits = &mut self.bits[self.word];
+            if *bits != 0 {
+                let bit = self.word * 64 + bits.trailing_zeros() as usize;
+                // clear the lowest set bit
+                *bits &= *bits - 1;
+                return Some(Key::from(bit as u8));
+            }
+            self.word += 1;
+        }
+        None
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.bits.iter().map(|b| b.count_ones() as usize).sum();
+        (len, Some(len))
+    }
+}
+
+impl ExactSizeIterator for Keys {}
+
+impl From<&[Key]> for KeyboardInput {
+    fn from(keys: &[Key]) -> Self {
+        let mut input = Self::new();
+        for key in keys {
+            input.set_key(*key);
+        }
+        input
     }
 }
 
 impl From<Vec<Key>> for KeyboardInput {
     fn from(keys: Vec<Key>) -> Self {
-        Self { keys }
+        Self::from(keys.as_slice())
     }
 }
 
-impl From<[u8; 10]> for KeyboardInput {
-    fn from(buffer: [u8; 10]) -> Self {
-        KeyboardInput::from(&buffer as &[u8])
+impl From<[u8; KEYBOARD_LENGTH]> for KeyboardInput {
+    /// panics if `buffer` is not a keyboard input, see `KeyboardInput::try_from` for a fallible version
+    fn from(buffer: [u8; KEYBOARD_LENGTH]) -> Self {
+        KeyboardInput::try_from(&buffer as &[u8]).unwrap_or_else(|e| panic!("{}", e))
     }
 }
 
-impl From<&[u8]> for KeyboardInput {
-    fn from(buffer: &[u8]) -> Self {
+impl TryFrom<&[u8]> for KeyboardInput {
+    type Error = PogpError;
+
+    /// decodes the first KEYBOARD_LENGTH bytes of `buffer`, the rest is ignored
+    fn try_from(buffer: &[u8]) -> Result<Self, Self::Error> {
+        if buffer.len() < KEYBOARD_LENGTH {
+            return Err(PogpError::Truncated {
+                expected: KEYBOARD_LENGTH,
+                actual: buffer.len(),
+            });
+        }
         if buffer[0] != InputType::Keyboard as u8 {
-            panic!(
-                "Expected first byte to be {}, but it was {}",
-                InputType::Keyboard as u8,
-                buffer[0]
-            );
+            return Err(PogpError::WrongInputType {
+                expected: InputType::Keyboard,
+                actual: buffer[0],
+            });
         }
-        let mut keys = Vec::new();
-        let mut byte_index = 0;
-        let mut bit_index = 0;
-        let mut current_enum: u8 = 0;
-
-        while byte_index < buffer.len() - 1 {
-            if buffer[byte_index + 1] & (1 << bit_index) > 0 {
-                keys.push(Key::from(current_enum));
-            }
-
-            bit_index += 1;
-            current_enum += 1;
-            if bit_index == 8 {
-                byte_index += 1;
-                bit_index = 0;
-            }
+        let mut input = Self::new();
+        for (byte_index, byte) in buffer[1..KEYBOARD_LENGTH].iter().enumerate() {
+            let bit = byte_index * 8;
+            input.bits[bit / 64] |= (*byte as u64) << (bit % 64);
         }
-        Self { keys }
+        // bit 0 is Key::Null, which is never held
+        input.bits[0] &= !1;
+        Ok(input)
     }
 }
 
@@ -73,14 +196,15 @@ mod keyboard_input {
     #[test]
     fn new() {
         let input = KeyboardInput::new();
-        assert_eq!(input.keys.len(), 0);
+        assert_eq!(input.len(), 0);
+        assert_eq!(input.is_empty(), true);
     }
 
     #[test]
     fn is_key_down() {
         let mut input = KeyboardInput::new();
         input.set_key(Key::KeyA);
-        assert_eq!(input.keys.len(), 1);
+        assert_eq!(input.len(), 1);
         assert_eq!(input.is_key_down(Key::KeyA), true);
     }
 
@@ -88,40 +212,136 @@ mod keyboard_input {
     fn set_key() {
         let mut input = KeyboardInput::new();
         input.set_key(Key::KeyB);
-        assert_eq!(input.keys.len(), 1);
+        assert_eq!(input.len(), 1);
         input.set_key(Key::KeyB);
-        assert_eq!(input.keys.len(), 1, "set key only sets once");
+        assert_eq!(input.len(), 1, "set key only sets once");
+    }
+
+    #[test]
+    fn is_key_down_high_bits() {
+        let mut input = KeyboardInput::new();
+        input.set_key(Key::IntlRo);
+        assert_eq!(input.is_key_down(Key::IntlRo), true, "second word");
+        assert_eq!(input.is_key_down(Key::Comma), false);
+    }
+
+    #[test]
+    fn keys() {
+        let input = KeyboardInput::from(vec![Key::AudioVolumeMute, Key::KeyA, Key::ArrowDown]);
+        let keys: Vec<Key> = input.keys().collect();
+        assert_eq!(keys, vec![Key::ArrowDown, Key::KeyA, Key::AudioVolumeMute]);
+        assert_eq!(input.keys().len(), 3);
+        assert_eq!(KeyboardInput::new().keys().next(), None);
+    }
+
+    #[test]
+    fn null_is_never_held() {
+        let mut buffer = [0; KEYBOARD_LENGTH];
+        buffer[0] = InputType::Keyboard as u8;
+        buffer[1] = 0b11;
+        let input = KeyboardInput::try_from(&buffer as &[u8]).unwrap();
+        assert_eq!(input.keys().collect::<Vec<_>>(), vec![Key::ArrowDown]);
+        assert_eq!(input.is_key_down(Key::Null), false, "bit 0 is masked");
+
+        let input = KeyboardInput::from(vec![Key::Null]);
+        assert_eq!(input.is_empty(), true, "set_key ignores Null");
+    }
+
+    #[test]
+    fn diff() {
+        let previous = KeyboardInput::from(vec![Key::KeyA, Key::KeyS, Key::F12]);
+        let input = KeyboardInput::from(vec![Key::KeyS, Key::KeyD, Key::Escape]);
+        let diff = input.diff(&previous);
+        assert_eq!(
+            diff.pressed.keys().collect::<Vec<_>>(),
+            vec![Key::KeyD, Key::Escape]
+        );
+        assert_eq!(
+            diff.released.keys().collect::<Vec<_>>(),
+            vec![Key::KeyA, Key::F12]
+        );
+        assert_eq!(
+            input.diff(&input),
+            KeyboardDiff::default(),
+            "empty on no change"
+        );
     }
 
     #[test]
     fn from_vec() {
         let input = KeyboardInput::from(vec![Key::Digit2]);
-        assert_eq!(input.keys.len(), 1);
+        assert_eq!(input.len(), 1);
         assert_eq!(input.is_key_down(Key::Digit2), true, "builds from vec");
     }
 
     #[test]
     fn from_u8array() {
-        let mut buffer: [u8; 10] = [0; 10];
+        let mut buffer = [0; KEYBOARD_LENGTH];
         buffer[0] = InputType::Keyboard as u8;
         buffer[1] |= 1 << 1;
         buffer[1] |= 1 << 2;
         buffer[4] |= 1 << 6;
         let input = KeyboardInput::from(buffer);
-        assert_eq!(input.keys.len(), 3);
-        assert_eq!(input.keys[0], Key::ArrowDown);
-        assert_eq!(input.keys[1], Key::ArrowLeft);
-        assert_eq!(input.keys[2], Key::Minus);
+        assert_eq!(input.len(), 3);
+        assert_eq!(input.is_key_down(Key::ArrowDown), true);
+        assert_eq!(input.is_key_down(Key::ArrowLeft), true);
+        assert_eq!(input.is_key_down(Key::Minus), true);
+    }
+
+    #[test]
+    fn to_bytes() {
+        let input = KeyboardInput::from(vec![Key::ArrowDown, Key::ArrowLeft, Key::Minus]);
+        let mut expected = [0; KEYBOARD_LENGTH];
+        expected[0] = InputType::Keyboard as u8;
+        expected[1] |= 1 << 1;
+        expected[1] |= 1 << 2;
+        expected[4] |= 1 << 6;
+        assert_eq!(input.to_bytes(), expected);
+    }
+
+    #[test]
+    fn from_typescript() {
+        // MarshalInput.encodeKeyboard with keys [1, 2, 25]
+        let buffer: [u8; 17] = [4, 6, 0, 0, 2, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0];
+        let input = KeyboardInput::from(buffer);
+        assert_eq!(input.len(), 3);
+        assert_eq!(input.to_bytes(), buffer);
+    }
+
+    #[test]
+    fn round_trip() {
+        let input = KeyboardInput::from(vec![Key::IntlRo, Key::Space, Key::KeyQ]);
+        let decoded = KeyboardInput::from(input.to_bytes());
+        assert_eq!(decoded.len(), 3);
+        assert_eq!(decoded.is_key_down(Key::Space), true);
+        assert_eq!(decoded.is_key_down(Key::KeyQ), true);
+        assert_eq!(decoded.is_key_down(Key::IntlRo), true);
+    }
+
+    #[test]
+    fn write_to_clears_buffer() {
+        let mut buffer: [u8; 20] = [0xff; 20];
+        KeyboardInput::from(vec![Key::Enter]).write_to(&mut buffer);
+        let decoded = KeyboardInput::try_from(&buffer as &[u8]).unwrap();
+        assert_eq!(decoded.len(), 1);
+        assert_eq!(decoded.is_key_down(Key::Enter), true);
+        assert_eq!(buffer[17], 0xff, "leaves bytes past the keyboard alone");
     }
 
     #[test]
     #[should_panic(expected = "first byte to be 4, but it was 3")]
     fn from_u8array_bad_input_type() {
-        let mut buffer: [u8; 10] = [0; 10];
+        let mut buffer = [0; KEYBOARD_LENGTH];
         buffer[0] = InputType::Mouse as u8;
         let _nope = KeyboardInput::from(buffer);
     }
 
+    #[test]
+    #[should_panic(expected = "Expected buffer of at least 17 bytes, but it was 3")]
+    fn from_bytes_unchecked_truncated() {
+        let _nope = KeyboardInput::from_bytes_unchecked(&[InputType::Keyboard as u8, 0, 0]);
+    }
+
     #[test]
     fn from_slice_ref() {
         let buffer: &mut [u8] = &mut [0; 20];
@@ -130,10 +350,42 @@ mod keyboard_input {
         buffer[1] |= 1 << 2;
         buffer[4] |= 1 << 6;
 
-        let input = KeyboardInput::from(buffer as &[u8]);
-        assert_eq!(input.keys.len(), 3);
-        assert_eq!(input.keys[0], Key::ArrowDown);
-        assert_eq!(input.keys[1], Key::ArrowLeft);
-        assert_eq!(input.keys[2], Key::Minus);
+        let input = KeyboardInput::try_from(buffer as &[u8]).unwrap();
+        assert_eq!(input.len(), 3);
+        assert_eq!(input.is_key_down(Key::ArrowDown), true);
+        assert_eq!(input.is_key_down(Key::ArrowLeft), true);
+        assert_eq!(input.is_key_down(Key::Minus), true);
+    }
+
+    #[test]
+    fn try_from_truncated() {
+        let buffer: [u8; 4] = [InputType::Keyboard as u8, 0, 0, 0];
+        assert_eq!(
+            KeyboardInput::try_from(&buffer as &[u8]).unwrap_err(),
+            PogpError::Truncated {
+                expected: 17,
+                actual: 4
+            }
+        );
+        assert_eq!(
+            KeyboardInput::try_from(&[] as &[u8]).unwrap_err(),
+            PogpError::Truncated {
+                expected: 17,
+                actual: 0
+            }
+        );
+    }
+
+    #[test]
+    fn try_from_bad_input_type() {
+        let mut buffer = [0; KEYBOARD_LENGTH];
+        buffer[0] = InputType::Mouse as u8;
+        assert_eq!(
+            KeyboardInput::try_from(&buffer as &[u8]).unwrap_err(),
+            PogpError::WrongInputType {
+                expected: InputType::Keyboard,
+                actual: 3
+            }
+        );
     }
 }
@@ -0,0 +1,171 @@
+use super::Key;
+
+/// keys whose printed character depends on the layout, row by row from the top
+const POSITIONS: [Key; 50] = [
+    Key::Backquote,
+    Key::Digit1,
+    Key::Digit2,
+    Key::Digit3,
+    Key::Digit4,
+    Key::Digit5,
+    Key::Digit6,
+    Key::Digit7,
+    Key::Digit8,
+    Key::Digit9,
+    Key::Digit0,
+    Key::Minus,
+    Key::Equal,
+    Key::IntlYen,
+    Key::KeyQ,
+    Key::KeyW,
+    Key::KeyE,
+    Key::KeyR,
+    Key::KeyT,
+    Key::KeyY,
+    Key::KeyU,
+    Key::KeyI,
+    Key::KeyO,
+    Key::KeyP,
+    Key::BracketLeft,
+    Key::BracketRight,
+    Key::Backslash,
+    Key::KeyA,
+    Key::KeyS,
+    Key::KeyD,
+    Key::KeyF,
+    Key::KeyG,
+    Key::KeyH,
+    Key::KeyJ,
+    Key::KeyK,
+    Key::KeyL,
+    Key::Semicolon,
+    Key::Quote,
+    Key::IntlBackslash,
+    Key::KeyZ,
+    Key::KeyX,
+    Key::KeyC,
+    Key::KeyV,
+    Key::KeyB,
+    Key::KeyN,
+    Key::KeyM,
+    Key::Comma,
+    Key::Period,
+    Key::Slash,
+    Key::IntlRo,
+];
+
+// characters for each of POSITIONS, a space is a key the layout doesn't have
+const US_QWERTY: &str = "`1234567890-= QWERTYUIOP[]\\ASDFGHJKL;' ZXCVBNM,./ ";
+const UK: &str = "`1234567890-= QWERTYUIOP[]#ASDFGHJKL;'\\ZXCVBNM,./ ";
+const AZERTY: &str = "²&é\"'(-è_çà)= AZERTYUIOP^$*QSDFGHJKLMù<WXCVBN,;:! ";
+const QWERTZ: &str = "^1234567890ß´ QWERTZUIOPÜ+#ASDFGHJKLÖÄ<YXCVBNM,.- ";
+const DVORAK: &str = "`1234567890[] ',.PYFGCRL/=\\AOEUIDHTNS- ;QJKXBMWVZ ";
+const JIS: &str = " 1234567890-^¥QWERTYUIOP@[]ASDFGHJKL;: ZXCVBNM,./\\";
+
+/// KeyboardLayout maps a physical `Key` to the character printed on it.
+///
+/// Use it to show players which key to press, e.g. KeyQ is labelled A on AZERTY. Letters are
+/// uppercase like on the keycap, and other keys are their unshifted character.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum KeyboardLayout {
+    #[default]
+    UsQwerty,
+    Uk,
+    /// french AZERTY
+    Azerty,
+    /// german QWERTZ
+    Qwertz,
+    /// US dvorak
+    Dvorak,
+    /// japanese
+    Jis,
+}
+
+impl KeyboardLayout {
+    /// the character printed on `key`, or None for keys that don't print a character
+    pub fn character(&self, key: Key) -> Option<char> {
+        if let Some(character) = numpad_character(key) {
+            return Some(character);
+        }
+        let index = POSITIONS.iter().position(|k| *k == key)?;
+        self.characters()
+            .chars()
+            .nth(index)
+            .filter(|character| *character != ' ')
+    }
+
+    fn characters(&self) -> &'static str {
+        match self {
+            KeyboardLayout::UsQwerty => US_QWERTY,
+            KeyboardLayout::Uk => UK,
+            KeyboardLayout::Azerty => AZERTY,
+            KeyboardLayout::Qwertz => QWERTZ,
+            KeyboardLayout::Dvorak => DVORAK,
+            KeyboardLayout::Jis => JIS,
+        }
+    }
+}
+
+/// the numpad is printed the same on every layout
+fn numpad_character(key: Key) -> Option<char> {
+    match key {
+        Key::Numpad0 => Some('0'),
+        Key::Numpad1 => Some('1'),
+        Key::Numpad2 => Some('2'),
+        Key::Numpad3 => Some('3'),
+        Key::Numpad4 => Some('4'),
+        Key::Numpad5 => Some('5'),
+        Key::Numpad6 => Some('6'),
+        Key::Numpad7 => Some('7'),
+        Key::Numpad8 => Some('8'),
+        Key::Numpad9 => Some('9'),
+        Key::NumpadAdd => Some('+'),
+        Key::NumpadSubtract => Some('-'),
+        Key::NumpadMultiply => Some('*'),
+        Key::NumpadDivide => Some('/'),
+        Key::NumpadDecimal => Some('.'),
+        Key::NumpadEqual => Some('='),
+        Key::NumpadComma => Some(','),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod keyboard_layout {
+    use super::*;
+
+    #[test]
+    fn layouts_cover_every_position() {
+        for layout in [US_QWERTY, UK, AZERTY, QWERTZ, DVORAK, JIS] {
+            assert_eq!(layout.chars().count(), POSITIONS.len(), "{}", layout);
+        }
+    }
+
+    #[test]
+    fn character() {
+        assert_eq!(KeyboardLayout::UsQwerty.character(Key::KeyQ), Some('Q'));
+        assert_eq!(KeyboardLayout::Azerty.character(Key::KeyQ), Some('A'));
+        assert_eq!(KeyboardLayout::Azerty.character(Key::Semicolon), Some('M'));
+        assert_eq!(KeyboardLayout::Qwertz.character(Key::KeyY), Some('Z'));
+        assert_eq!(KeyboardLayout::Dvorak.character(Key::KeyS), Some('O'));
+        assert_eq!(KeyboardLayout::Uk.character(Key::Backslash), Some('#'));
+        assert_eq!(KeyboardLayout::Jis.character(Key::IntlYen), Some('¥'));
+    }
+
+    #[test]
+    fn missing_keys() {
+        assert_eq!(
+            KeyboardLayout::UsQwerty.character(Key::IntlBackslash),
+            None,
+            "ansi keyboards don't have the iso key"
+        );
+        assert_eq!(KeyboardLayout::UsQwerty.character(Key::Space), None);
+        assert_eq!(KeyboardLayout::Jis.character(Key::Backquote), None);
+    }
+
+    #[test]
+    fn numpad() {
+        assert_eq!(KeyboardLayout::Azerty.character(Key::Numpad1), Some('1'));
+        assert_eq!(KeyboardLayout::Dvorak.character(Key::NumpadAdd), Some('+'));
+    }
+}
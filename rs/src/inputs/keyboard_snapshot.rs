@@ -1,34 +1,103 @@
 use super::Key;
+use super::KeyChord;
+use super::KeyboardDiff;
 use super::KeyboardInput;
+use super::Keys;
+use super::KEYBOARD_KEY_COUNT;
 
 const INPUT_BUFFER_LENGTH: usize = 10;
+/// most taps `tap_count` can report
+pub const MAX_TAP_COUNT: usize = 4;
 
+/// KeyboardSnapshot keeps the last `N` keyboard inputs to answer questions across frames, e.g.
+/// whether a key went down this frame.
+///
+/// Use `KeyboardSnapshot::new()` for the default history of 10 frames, or
+/// `KeyboardSnapshot::<N>::default()` to look further back. `N` must be at least 2.
 #[derive(Debug)]
-pub struct KeyboardSnapshot {
-    inputs: [KeyboardInput; INPUT_BUFFER_LENGTH],
+pub struct KeyboardSnapshot<const N: usize = INPUT_BUFFER_LENGTH> {
+    inputs: [KeyboardInput; N],
     index: usize,
+    len: usize,
+    /// consecutive frames each key has been held, indexed by Key
+    held: [u32; KEYBOARD_KEY_COUNT],
+    /// frames since each key was released, None while held or if it never was
+    released: [Option<u32>; KEYBOARD_KEY_COUNT],
+    /// frames of the latest presses of each key, newest first
+    presses: [[Option<u64>; MAX_TAP_COUNT]; KEYBOARD_KEY_COUNT],
+    /// number of inputs added
+    frame: u64,
 }
 
 impl KeyboardSnapshot {
     pub fn new() -> Self {
-        Self {
-            inputs: Default::default(),
-            index: 0,
-        }
+        Self::default()
     }
+}
 
+impl<const N: usize> KeyboardSnapshot<N> {
     pub fn add_input(&mut self, input: KeyboardInput) {
         self.inputs[self.index] = input;
+        self.len = (self.len + 1).min(N);
         self.advance_index();
+
+        for id in 0..KEYBOARD_KEY_COUNT {
+            if input.is_key_down(Key::from(id as u8)) {
+                if self.held[id] == 0 {
+                    self.presses[id].rotate_right(1);
+                    self.presses[id][0] = Some(self.frame);
+                }
+                self.held[id] = self.held[id].saturating_add(1);
+                self.released[id] = None;
+            } else {
+                self.released[id] = match self.held[id] {
+                    0 => self.released[id].map(|frames| frames.saturating_add(1)),
+                    _ => Some(0),
+                };
+                self.held[id] = 0;
+            }
+        }
+        self.frame += 1;
+    }
+
+    /// number of frames in a row `key` has been held including the latest, 0 if it isn't held.
+    /// Unlike `was_key_down` this isn't limited to the history length.
+    pub fn held_frames(&self, key: Key) -> u32 {
+        self.held[key as usize]
+    }
+
+    /// on the frame `key` is pressed, how many presses in a row it's been with at most `window`
+    /// frames from one press to the next, e.g. 2 for a double tap. 0 on any other frame.
+    ///
+    /// A press more than `window` frames after the one before starts over at 1, so a triple tap
+    /// reports 1, 2 and 3 on each press. Counts stop at MAX_TAP_COUNT.
+    pub fn tap_count(&self, key: Key, window: u32) -> u32 {
+        if self.held_frames(key) != 1 {
+            return 0;
+        }
+        let presses = &self.presses[key as usize];
+        let taps = presses
+            .windows(2)
+            .take_while(|pair| match (pair[0], pair[1]) {
+                (Some(newer), Some(older)) => newer - older <= window as u64,
+                _ => false,
+            })
+            .count();
+        taps as u32 + 1
+    }
+
+    /// number of frames since `key` was released, 0 on the frame it's released. None while it's
+    /// held or if it hasn't been released yet.
+    pub fn frames_since_release(&self, key: Key) -> Option<u32> {
+        self.released[key as usize]
     }
 
     pub fn is_key_down(&self, key: Key) -> bool {
-        self.inputs[self.head()].is_key_down(key)
-            && !self.inputs[self.last_frame()].is_key_down(key)
+        self.was_key_down(key, 0) && !self.was_key_down(key, 1)
     }
 
     pub fn is_key_pressed(&self, key: Key) -> bool {
-        self.inputs[self.head()].is_key_down(key)
+        self.was_key_down(key, 0)
     }
 
     pub fn is_key(&self, key: Key) -> bool {
@@ -36,31 +105,84 @@ impl KeyboardSnapshot {
     }
 
     pub fn is_key_up(&self, key: Key) -> bool {
-        !self.inputs[self.head()].is_key_down(key)
-            && self.inputs[self.last_frame()].is_key_down(key)
+        !self.was_key_down(key, 0) && self.was_key_down(key, 1)
+    }
+
+    /// whether `key` was held `frames_ago` frames before the latest input
+    pub fn was_key_down(&self, key: Key, frames_ago: usize) -> bool {
+        self.past_input(frames_ago)
+            .map_or(false, |input| input.is_key_down(key))
+    }
+
+    /// true on the frame `chord` is completed, whether the key or a modifier was pressed last
+    pub fn is_chord_down(&self, chord: &KeyChord) -> bool {
+        self.is_chord_pressed(chord)
+            && !self
+                .past_input(1)
+                .map_or(false, |input| chord.is_held(input))
+    }
+
+    pub fn is_chord_pressed(&self, chord: &KeyChord) -> bool {
+        self.past_input(0)
+            .map_or(false, |input| chord.is_held(input))
+    }
+
+    /// keys that went down this frame
+    pub fn pressed_this_frame(&self) -> Keys {
+        self.diff().pressed.keys()
+    }
+
+    /// keys that came up this frame
+    pub fn released_this_frame(&self) -> Keys {
+        self.diff().released.keys()
+    }
+
+    fn diff(&self) -> KeyboardDiff {
+        let empty = KeyboardInput::new();
+        let latest = self.past_input(0).unwrap_or(&empty);
+        latest.diff(self.past_input(1).unwrap_or(&empty))
+    }
+
+    /// inputs in the history from newest to oldest, at most `N`
+    pub fn frames(&self) -> impl Iterator<Item = &KeyboardInput> {
+        (0..self.len).map(|frames_ago| &self.inputs[self.past_frame(frames_ago)])
+    }
+
+    fn past_input(&self, frames_ago: usize) -> Option<&KeyboardInput> {
+        if frames_ago >= self.len {
+            return None;
+        }
+        Some(&self.inputs[self.past_frame(frames_ago)])
     }
 
     fn advance_index(&mut self) {
-        self.index = if self.index == INPUT_BUFFER_LENGTH - 1 {
+        self.index = if self.index == N - 1 {
             0
         } else {
             self.index + 1
         }
     }
 
-    fn last_frame(&self) -> usize {
-        match self.index {
-            0 => INPUT_BUFFER_LENGTH - 1,
-            1 => INPUT_BUFFER_LENGTH - 2,
-            _ => self.index - 2,
-        }
+    /// evaluated when a snapshot is created, so too few frames fail to compile
+    const ENOUGH_FRAMES: () = assert!(N >= 2, "KeyboardSnapshot needs at least 2 frames");
+
+    fn past_frame(&self, frames_ago: usize) -> usize {
+        (self.index + N * 2 - 1 - frames_ago) % N
     }
+}
 
-    fn head(&self) -> usize {
-        if self.index == 0 {
-            INPUT_BUFFER_LENGTH - 1
-        } else {
-            self.index - 1
+impl<const N: usize> Default for KeyboardSnapshot<N> {
+    fn default() -> Self {
+        #[allow(clippy::let_unit_value)]
+        let () = Self::ENOUGH_FRAMES;
+        Self {
+            inputs: [KeyboardInput::new(); N],
+            index: 0,
+            len: 0,
+            held: [0; KEYBOARD_KEY_COUNT],
+            released: [None; KEYBOARD_KEY_COUNT],
+            presses: [[None; MAX_TAP_COUNT]; KEYBOARD_KEY_COUNT],
+            frame: 0,
         }
     }
 }
@@ -152,4 +274,229 @@ mod keyboard_snapshot {
         snapshot.add_input(Default::default());
         assert_eq!(snapshot.is_key_up(Key::Enter), false, "false on next frame");
     }
+
+    #[test]
+    fn is_chord_down() {
+        let chord: KeyChord = "Ctrl+Shift+S".parse().unwrap();
+        let mut snapshot = KeyboardSnapshot::new();
+        assert_eq!(snapshot.is_chord_down(&chord), false, "false on empty");
+
+        snapshot.add_input(KeyboardInput::from(vec![Key::ControlLeft]));
+        snapshot.add_input(KeyboardInput::from(vec![Key::ControlLeft, Key::KeyS]));
+        assert_eq!(
+            snapshot.is_chord_down(&chord),
+            false,
+            "false on partial chord"
+        );
+
+        snapshot.add_input(KeyboardInput::from(vec![
+            Key::ControlLeft,
+            Key::ShiftLeft,
+            Key::KeyS,
+        ]));
+        assert_eq!(
+            snapshot.is_chord_down(&chord),
+            true,
+            "true when the last modifier completes it"
+        );
+        assert_eq!(snapshot.is_chord_pressed(&chord), true);
+
+        snapshot.add_input(KeyboardInput::from(vec![
+            Key::ControlLeft,
+            Key::ShiftLeft,
+            Key::KeyS,
+        ]));
+        assert_eq!(snapshot.is_chord_down(&chord), false, "false while held");
+        assert_eq!(snapshot.is_chord_pressed(&chord), true);
+    }
+
+    #[test]
+    fn is_key_down_wraps() {
+        let mut snapshot = KeyboardSnapshot::new();
+        for _ in 0..INPUT_BUFFER_LENGTH - 1 {
+            snapshot.add_input(Default::default());
+        }
+        snapshot.add_input(KeyboardInput::from(vec![Key::Space]));
+        assert_eq!(
+            snapshot.is_key_down(Key::Space),
+            true,
+            "true on the last slot of the buffer"
+        );
+
+        snapshot.add_input(KeyboardInput::from(vec![Key::Space]));
+        assert_eq!(
+            snapshot.is_key_down(Key::Space),
+            false,
+            "false while key down on the first slot"
+        );
+    }
+
+    #[test]
+    fn was_key_down() {
+        let mut snapshot: KeyboardSnapshot<30> = Default::default();
+        snapshot.add_input(KeyboardInput::from(vec![Key::KeyJ]));
+        for _ in 0..20 {
+            snapshot.add_input(Default::default());
+        }
+        assert_eq!(snapshot.was_key_down(Key::KeyJ, 20), true);
+        assert_eq!(snapshot.was_key_down(Key::KeyJ, 19), false);
+        assert_eq!(
+            snapshot.was_key_down(Key::KeyJ, 21),
+            false,
+            "false before the first input"
+        );
+        assert_eq!(
+            snapshot.was_key_down(Key::KeyJ, 30),
+            false,
+            "false past the history"
+        );
+    }
+
+    #[test]
+    fn frames() {
+        let mut snapshot: KeyboardSnapshot<3> = Default::default();
+        assert_eq!(snapshot.frames().count(), 0, "empty on start");
+
+        for key in [Key::KeyA, Key::KeyB, Key::KeyC, Key::KeyD] {
+            snapshot.add_input(KeyboardInput::from(vec![key]));
+        }
+        let frames: Vec<_> = snapshot.frames().collect();
+        assert_eq!(frames.len(), 3);
+        assert_eq!(frames[0].is_key_down(Key::KeyD), true, "newest first");
+        assert_eq!(frames[2].is_key_down(Key::KeyB), true);
+    }
+
+    #[test]
+    fn held_frames() {
+        let mut snapshot = KeyboardSnapshot::new();
+        assert_eq!(snapshot.held_frames(Key::KeyF), 0, "0 on empty");
+
+        for _ in 0..25 {
+            snapshot.add_input(KeyboardInput::from(vec![Key::KeyF]));
+        }
+        assert_eq!(
+            snapshot.held_frames(Key::KeyF),
+            25,
+            "counts past the history length"
+        );
+
+        snapshot.add_input(Default::default());
+        assert_eq!(snapshot.held_frames(Key::KeyF), 0, "0 once released");
+
+        snapshot.add_input(KeyboardInput::from(vec![Key::KeyF]));
+        assert_eq!(snapshot.held_frames(Key::KeyF), 1, "restarts on press");
+    }
+
+    #[test]
+    fn frames_since_release() {
+        let mut snapshot = KeyboardSnapshot::new();
+        snapshot.add_input(Default::default());
+        assert_eq!(
+            snapshot.frames_since_release(Key::Space),
+            None,
+            "none if never pressed"
+        );
+
+        snapshot.add_input(KeyboardInput::from(vec![Key::Space]));
+        assert_eq!(
+            snapshot.frames_since_release(Key::Space),
+            None,
+            "none while held"
+        );
+
+        snapshot.add_input(Default::default());
+        assert_eq!(
+            snapshot.frames_since_release(Key::Space),
+            Some(0),
+            "0 on release"
+        );
+
+        for _ in 0..3 {
+            snapshot.add_input(Default::default());
+        }
+        assert_eq!(snapshot.frames_since_release(Key::Space), Some(3));
+    }
+
+    fn tap(snapshot: &mut KeyboardSnapshot, key: Key, gap: usize) {
+        snapshot.add_input(KeyboardInput::from(vec![key]));
+        for _ in 1..gap {
+            snapshot.add_input(Default::default());
+        }
+    }
+
+    #[test]
+    fn tap_count() {
+        let mut snapshot = KeyboardSnapshot::new();
+        assert_eq!(snapshot.tap_count(Key::KeyD, 10), 0, "0 on empty");
+
+        snapshot.add_input(KeyboardInput::from(vec![Key::KeyD]));
+        assert_eq!(snapshot.tap_count(Key::KeyD, 10), 1, "1 on first press");
+
+        snapshot.add_input(KeyboardInput::from(vec![Key::KeyD]));
+        assert_eq!(snapshot.tap_count(Key::KeyD, 10), 0, "0 while held");
+
+        for _ in 0..5 {
+            snapshot.add_input(Default::default());
+        }
+        snapshot.add_input(KeyboardInput::from(vec![Key::KeyD]));
+        assert_eq!(
+            snapshot.tap_count(Key::KeyD, 10),
+            2,
+            "2 on press inside the window"
+        );
+        assert_eq!(
+            snapshot.tap_count(Key::KeyD, 5),
+            1,
+            "1 when the window is too short"
+        );
+
+        snapshot.add_input(Default::default());
+        snapshot.add_input(KeyboardInput::from(vec![Key::KeyD]));
+        assert_eq!(snapshot.tap_count(Key::KeyD, 10), 3, "3 on triple tap");
+    }
+
+    #[test]
+    fn tap_count_resets() {
+        let mut snapshot = KeyboardSnapshot::new();
+        tap(&mut snapshot, Key::KeyA, 4);
+        tap(&mut snapshot, Key::KeyA, 20);
+        snapshot.add_input(KeyboardInput::from(vec![Key::KeyA]));
+        assert_eq!(
+            snapshot.tap_count(Key::KeyA, 10),
+            1,
+            "press outside the window starts over"
+        );
+
+        for _ in 0..10 {
+            tap(&mut snapshot, Key::KeyA, 2);
+        }
+        snapshot.add_input(KeyboardInput::from(vec![Key::KeyA]));
+        assert_eq!(
+            snapshot.tap_count(Key::KeyA, 10),
+            MAX_TAP_COUNT as u32,
+            "stops at the max"
+        );
+    }
+
+    #[test]
+    fn pressed_and_released_this_frame() {
+        let mut snapshot = KeyboardSnapshot::new();
+        assert_eq!(snapshot.pressed_this_frame().count(), 0, "empty on start");
+
+        snapshot.add_input(KeyboardInput::from(vec![Key::KeyW, Key::ShiftLeft]));
+        assert_eq!(
+            snapshot.pressed_this_frame().collect::<Vec<_>>(),
+            vec![Key::ShiftLeft, Key::KeyW]
+        );
+
+        snapshot.add_input(KeyboardInput::from(vec![Key::KeyW, Key::Space]));
+        assert_eq!(
+            snapshot.pressed_this_frame().collect::<Vec<_>>(),
+            vec![Key::Space]
+        );
+        assert_eq!(
+            snapshot.released_this_frame().collect::<Vec<_>>(),
+            vec![Key::ShiftLeft]
+        );
+    }
 }
@@ -5,7 +5,72 @@ mod input_type;
 pub use input_type::InputType;
 
 mod keyboard_input;
-pub use keyboard_input::KeyboardInput;
+pub use keyboard_input::{KeyboardDiff, KeyboardInput, Keys, KEYBOARD_KEY_COUNT, KEYBOARD_LENGTH};
 
 mod keyboard_snapshot;
-pub use keyboard_snapshot::KeyboardSnapshot;
+pub use keyboard_snapshot::{KeyboardSnapshot, MAX_TAP_COUNT};
+
+mod key_repeat;
+pub use key_repeat::KeyRepeat;
+
+mod modifiers;
+pub use modifiers::Modifiers;
+
+mod key_chord;
+pub use key_chord::KeyChord;
+
+mod keyboard_layout;
+pub use keyboard_layout::KeyboardLayout;
+
+mod button_position;
+pub use button_position::ButtonPosition;
+
+mod hand;
+pub use hand::Hand;
+
+mod gamepad_input;
+pub use gamepad_input::{GamepadAxes, GamepadButton, GamepadInput, DEFAULT_DEAD_ZONE, STICK_MAX};
+
+mod gamepad_snapshot;
+pub use gamepad_snapshot::GamepadSnapshot;
+
+mod mouse_input;
+pub use mouse_input::{MouseInput, MOUSE_LENGTH};
+
+mod mouse_snapshot;
+pub use mouse_snapshot::MouseSnapshot;
+
+mod touch_input;
+pub use touch_input::{Finger, TouchInput, MAX_FINGERS};
+
+mod touch_snapshot;
+pub use touch_snapshot::{Touch, TouchPhase, TouchSnapshot, DEFAULT_MAX_TOUCH_DISTANCE};
+
+mod custom_input;
+pub use custom_input::{CustomField, CustomInput};
+
+mod action_map;
+pub use action_map::{ActionMap, Binding, BindingSource};
+
+mod binding_capture;
+pub use binding_capture::{BindingCapture, CaptureState};
+
+mod binding_profile;
+pub use binding_profile::{BindingProfile, BINDING_PROFILE_VERSION};
+
+mod axis;
+pub use axis::{Axis1d, Axis2d, OppositePolicy, Vector2};
+
+mod motion;
+pub use motion::{
+    Direction, DirectionKeys, Facing, GamepadMotionSource, KeyboardMotionSource, Motion,
+    MotionMatch, MotionRecognizer, MotionSource, MotionStep,
+};
+
+mod input_frame;
+pub use input_frame::{
+    InputFrame, GAMEPAD_OFFSET, MAX_AXES, MAX_BUTTONS, MAX_FRAME_LENGTH, MOUSE_OFFSET,
+};
+
+mod binary_reader;
+mod binary_writer;
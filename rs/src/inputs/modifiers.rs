@@ -0,0 +1,96 @@
+use std::ops::{BitOr, BitOrAssign};
+
+use super::{Key, KeyboardInput};
+
+/// Modifiers is the set of modifier keys held down, without distinguishing left from right.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Modifiers(u8);
+
+impl Modifiers {
+    pub const NONE: Modifiers = Modifiers(0);
+    pub const CTRL: Modifiers = Modifiers(1 << 0);
+    pub const ALT: Modifiers = Modifiers(1 << 1);
+    pub const SHIFT: Modifiers = Modifiers(1 << 2);
+    pub const META: Modifiers = Modifiers(1 << 3);
+
+    pub const fn is_empty(&self) -> bool {
+        self.0 == 0
+    }
+
+    pub const fn contains(&self, other: Modifiers) -> bool {
+        self.0 & other.0 == other.0
+    }
+
+    pub fn insert(&mut self, other: Modifiers) {
+        self.0 |= other.0
+    }
+
+    /// the modifier `key` sets, if it's a modifier key
+    pub fn from_key(key: Key) -> Option<Modifiers> {
+        match key {
+            Key::ControlLeft | Key::ControlRight => Some(Self::CTRL),
+            Key::AltLeft | Key::AltRight => Some(Self::ALT),
+            Key::ShiftLeft | Key::ShiftRight => Some(Self::SHIFT),
+            Key::MetaLeft | Key::MetaRight => Some(Self::META),
+            _ => None,
+        }
+    }
+}
+
+impl BitOr for Modifiers {
+    type Output = Self;
+
+    fn bitor(self, rhs: Self) -> Self {
+        Self(self.0 | rhs.0)
+    }
+}
+
+impl BitOrAssign for Modifiers {
+    fn bitor_assign(&mut self, rhs: Self) {
+        self.insert(rhs)
+    }
+}
+
+impl From<&KeyboardInput> for Modifiers {
+    fn from(input: &KeyboardInput) -> Self {
+        [
+            Key::ControlLeft,
+            Key::ControlRight,
+            Key::AltLeft,
+            Key::AltRight,
+            Key::ShiftLeft,
+            Key::ShiftRight,
+            Key::MetaLeft,
+            Key::MetaRight,
+        ]
+        .into_iter()
+        .filter(|key| input.is_key_down(*key))
+        .filter_map(Modifiers::from_key)
+        .fold(Modifiers::NONE, |modifiers, m| modifiers | m)
+    }
+}
+
+#[cfg(test)]
+mod modifiers {
+    use super::*;
+
+    #[test]
+    fn from_keyboard_input() {
+        let input = KeyboardInput::from(vec![Key::ControlRight, Key::ShiftLeft, Key::KeyS]);
+        let modifiers = Modifiers::from(&input);
+        assert_eq!(modifiers, Modifiers::CTRL | Modifiers::SHIFT);
+        assert_eq!(modifiers.contains(Modifiers::CTRL), true);
+        assert_eq!(modifiers.contains(Modifiers::ALT), false);
+        assert_eq!(
+            Modifiers::from(&KeyboardInput::new()).is_empty(),
+            true,
+            "empty without modifier keys"
+        );
+    }
+
+    #[test]
+    fn left_and_right() {
+        let both = KeyboardInput::from(vec![Key::AltLeft, Key::AltRight]);
+        assert_eq!(Modifiers::from(&both), Modifiers::ALT);
+    }
+}
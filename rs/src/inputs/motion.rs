@@ -0,0 +1,610 @@
+use super::{ButtonPosition, GamepadSnapshot, Hand, Key, KeyboardSnapshot, DEFAULT_DEAD_ZONE};
+
+/// frames allowed between steps of a motion unless it sets its own window
+const DEFAULT_WINDOW: u32 = 8;
+
+/// Direction of a stick or d-pad in numpad notation, relative to the way the player faces, so
+/// Forward is 6 and a quarter circle forward is 2, 3, 6.
+#[repr(u8)]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum Direction {
+    DownBack = 1,
+    Down = 2,
+    DownForward = 3,
+    Back = 4,
+    #[default]
+    Neutral = 5,
+    Forward = 6,
+    UpBack = 7,
+    Up = 8,
+    UpForward = 9,
+}
+
+impl Direction {
+    /// the direction for a numpad digit, e.g. 6 is Forward
+    pub fn from_numpad(digit: u8) -> Option<Direction> {
+        match digit {
+            1 => Some(Direction::DownBack),
+            2 => Some(Direction::Down),
+            3 => Some(Direction::DownForward),
+            4 => Some(Direction::Back),
+            5 => Some(Direction::Neutral),
+            6 => Some(Direction::Forward),
+            7 => Some(Direction::UpBack),
+            8 => Some(Direction::Up),
+            9 => Some(Direction::UpForward),
+            _ => None,
+        }
+    }
+
+    /// the direction for a horizontal and vertical component of -1, 0 or 1, where x is positive
+    /// forward and y is positive down like screen coordinates
+    pub fn from_components(x: i8, y: i8) -> Direction {
+        let column = x.signum() + 1;
+        let row = 1 - y.signum();
+        // rows of the numpad from the bottom
+        Self::from_numpad((row * 3 + column + 1) as u8).unwrap_or_default()
+    }
+
+    /// the same direction for a player facing the other way
+    pub fn mirrored(&self) -> Direction {
+        match self {
+            Direction::DownBack => Direction::DownForward,
+            Direction::DownForward => Direction::DownBack,
+            Direction::Back => Direction::Forward,
+            Direction::Forward => Direction::Back,
+            Direction::UpBack => Direction::UpForward,
+            Direction::UpForward => Direction::UpBack,
+            other => *other,
+        }
+    }
+}
+
+/// which way the player faces, Forward is right when facing right
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum Facing {
+    #[default]
+    Right,
+    Left,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum MotionStep<B> {
+    /// the stick moves to this direction
+    Direction(Direction),
+    /// the button goes down
+    Press(B),
+}
+
+impl<B> MotionStep<B> {
+    /// direction steps from numpad notation, e.g. "236" for a quarter circle forward. None if
+    /// `notation` has anything but the digits 1 to 9.
+    pub fn directions(notation: &str) -> Option<Vec<MotionStep<B>>> {
+        notation
+            .bytes()
+            .map(|digit| {
+                digit
+                    .checked_sub(b'0')
+                    .and_then(Direction::from_numpad)
+                    .map(MotionStep::Direction)
+            })
+            .collect()
+    }
+}
+
+/// Motion is a command and the steps that trigger it, e.g. a fireball on 2, 3, 6 then punch.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Motion<C, B> {
+    pub command: C,
+    pub steps: Vec<MotionStep<B>>,
+    /// most frames allowed from one step to the next
+    pub window: u32,
+    /// when several motions complete on the same frame the highest priority fires
+    pub priority: i32,
+}
+
+impl<C, B> Motion<C, B> {
+    pub fn new(command: C, steps: Vec<MotionStep<B>>) -> Self {
+        Self {
+            command,
+            steps,
+            window: DEFAULT_WINDOW,
+            priority: 0,
+        }
+    }
+
+    pub fn with_window(self, window: u32) -> Self {
+        Self { window, ..self }
+    }
+
+    pub fn with_priority(self, priority: i32) -> Self {
+        Self { priority, ..self }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct MotionMatch<C> {
+    pub command: C,
+    /// frame the motion completed on, counting from the first update
+    pub frame: u64,
+}
+
+/// MotionSource is the input history a MotionRecognizer reads each frame, implemented over the
+/// snapshots.
+pub trait MotionSource<B> {
+    /// direction held `frames_ago` frames before the latest input with Forward as right
+    fn direction(&self, frames_ago: usize) -> Direction;
+    /// whether `button` went down `frames_ago` frames before the latest input
+    fn is_pressed(&self, button: B, frames_ago: usize) -> bool;
+    /// how many frames back the source can look, including the latest
+    fn history_length(&self) -> usize;
+}
+
+/// keys that move the stick when recognizing motions on a keyboard
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct DirectionKeys {
+    pub up: Key,
+    pub down: Key,
+    pub left: Key,
+    pub right: Key,
+}
+
+impl DirectionKeys {
+    pub const ARROWS: DirectionKeys = DirectionKeys {
+        up: Key::ArrowUp,
+        down: Key::ArrowDown,
+        left: Key::ArrowLeft,
+        right: Key::ArrowRight,
+    };
+    pub const WASD: DirectionKeys = DirectionKeys {
+        up: Key::KeyW,
+        down: Key::KeyS,
+        left: Key::KeyA,
+        right: Key::KeyD,
+    };
+}
+
+impl Default for DirectionKeys {
+    fn default() -> Self {
+        Self::ARROWS
+    }
+}
+
+/// reads motions from a keyboard, opposite keys held together cancel out
+pub struct KeyboardMotionSource<'a, const N: usize = 10> {
+    pub snapshot: &'a KeyboardSnapshot<N>,
+    pub keys: DirectionKeys,
+}
+
+impl<const N: usize> MotionSource<Key> for KeyboardMotionSource<'_, N> {
+    fn direction(&self, frames_ago: usize) -> Direction {
+        let axis = |negative: Key, positive: Key| {
+            self.snapshot.was_key_down(positive, frames_ago) as i8
+                - self.snapshot.was_key_down(negative, frames_ago) as i8
+        };
+        Direction::from_components(
+            axis(self.keys.left, self.keys.right),
+            axis(self.keys.up, self.keys.down),
+        )
+    }
+
+    fn is_pressed(&self, key: Key, frames_ago: usize) -> bool {
+        self.snapshot.was_key_down(key, frames_ago)
+            && !self.snapshot.was_key_down(key, frames_ago + 1)
+    }
+
+    fn history_length(&self) -> usize {
+        N
+    }
+}
+
+/// reads motions from a gamepad's d-pad, or its stick for `hand` when the d-pad is released
+pub struct GamepadMotionSource<'a, const N: usize = 10> {
+    pub snapshot: &'a GamepadSnapshot<N>,
+    pub hand: Hand,
+    /// stick values closer to the center than this count as neutral
+    pub dead_zone: i64,
+}
+
+impl<'a, const N: usize> GamepadMotionSource<'a, N> {
+    pub fn new(snapshot: &'a GamepadSnapshot<N>) -> Self {
+        Self {
+            snapshot,
+            hand: Hand::Left,
+            dead_zone: DEFAULT_DEAD_ZONE,
+        }
+    }
+}
+
+impl<const N: usize> MotionSource<ButtonPosition> for GamepadMotionSource<'_, N> {
+    fn direction(&self, frames_ago: usize) -> Direction {
+        let axis = |negative: ButtonPosition, positive: ButtonPosition| {
+            self.snapshot.is_button(positive, frames_ago) as i8
+                - self.snapshot.is_button(negative, frames_ago) as i8
+        };
+        let x = axis(ButtonPosition::LeftFaceLeft, ButtonPosition::LeftFaceRight);
+        let y = axis(ButtonPosition::LeftFaceTop, ButtonPosition::LeftFaceBottom);
+        if x != 0 || y != 0 {
+            return Direction::from_components(x, y);
+        }
+
+        let stick = |value: i64| match value {
+            v if v > self.dead_zone => 1,
+            v if v < -self.dead_zone => -1,
+            _ => 0,
+        };
+        self.snapshot
+            .past_axes(self.hand, frames_ago)
+            .map_or(Direction::Neutral, |axes| {
+                Direction::from_components(stick(axes.x), stick(axes.y))
+            })
+    }
+
+    fn is_pressed(&self, position: ButtonPosition, frames_ago: usize) -> bool {
+        self.snapshot.is_button(position, frames_ago)
+            && !self.snapshot.is_button(position, frames_ago + 1)
+    }
+
+    fn history_length(&self) -> usize {
+        N
+    }
+}
+
+/// MotionRecognizer matches motions like quarter circle forward + punch against the input of
+/// each frame.
+///
+/// Each step of a motion has to happen within the motion's window of frames after the step
+/// before, with other inputs allowed in between, and the last step has to happen on the current
+/// frame so a motion fires once.
+///
+/// Past frames are read from the snapshot behind the source, so it needs a history as long as
+/// the longest motion: `steps * window + 1` frames, e.g. `KeyboardSnapshot::<33>` for a quarter
+/// circle and punch with the default window. Steps older than the snapshot's history are missed.
+#[derive(Debug)]
+pub struct MotionRecognizer<C, B> {
+    motions: Vec<Motion<C, B>>,
+    facing: Facing,
+    frame: u64,
+}
+
+impl<C: Clone, B: Copy> MotionRecognizer<C, B> {
+    pub fn new(motions: Vec<Motion<C, B>>) -> Self {
+        Self {
+            motions,
+            facing: Facing::Right,
+            frame: 0,
+        }
+    }
+
+    pub fn facing(&self) -> Facing {
+        self.facing
+    }
+
+    /// which way the player faces, so Forward follows them when they cross sides
+    pub fn set_facing(&mut self, facing: Facing) {
+        self.facing = facing;
+    }
+
+    /// reads the input from `source` and returns the motion that completed on its latest frame,
+    /// if any. Call once per frame after adding the frame's input to the snapshot.
+    pub fn update(&mut self, source: &impl MotionSource<B>) -> Option<MotionMatch<C>> {
+        let frame = self.frame;
+        self.frame += 1;
+
+        // reversed so the first motion declared wins a tie
+        self.motions
+            .iter()
+            .rev()
+            .filter(|motion| self.is_match(motion, source))
+            .max_by_key(|motion| (motion.priority, motion.steps.len()))
+            .map(|motion| MotionMatch {
+                command: motion.command.clone(),
+                frame,
+            })
+    }
+
+    fn is_match(&self, motion: &Motion<C, B>, source: &impl MotionSource<B>) -> bool {
+        let Some((last, rest)) = motion.steps.split_last() else {
+            return false;
+        };
+        let starts_now = match last {
+            MotionStep::Direction(direction) => {
+                self.direction(source, 0) == *direction
+                    && (source.history_length() < 2 || self.direction(source, 1) != *direction)
+            }
+            MotionStep::Press(_) => self.is_step(last, source, 0),
+        };
+        if !starts_now {
+            return false;
+        }
+
+        // walk back from the last step, matching each step to the latest frame it fits
+        let mut frames_ago = 0;
+        let mut next = last;
+        for step in rest.iter().rev() {
+            // a direction and a press can share a frame, two of the same kind can't
+            let same_kind = matches!(
+                (step, next),
+                (MotionStep::Direction(_), MotionStep::Direction(_))
+                    | (MotionStep::Press(_), MotionStep::Press(_))
+            );
+            let start = frames_ago + same_kind as usize;
+            let end = (frames_ago + motion.window as usize).min(source.history_length() - 1);
+            match (start..=end).find(|ago| self.is_step(step, source, *ago)) {
+                Some(ago) => frames_ago = ago,
+                None => return false,
+            }
+            next = step;
+        }
+        true
+    }
+
+    fn direction(&self, source: &impl MotionSource<B>, frames_ago: usize) -> Direction {
+        match self.facing {
+            Facing::Right => source.direction(frames_ago),
+            Facing::Left => source.direction(frames_ago).mirrored(),
+        }
+    }
+
+    fn is_step(
+        &self,
+        step: &MotionStep<B>,
+        source: &impl MotionSource<B>,
+        frames_ago: usize,
+    ) -> bool {
+        match step {
+            MotionStep::Direction(d) => self.direction(source, frames_ago) == *d,
+            MotionStep::Press(button) => source.is_pressed(*button, frames_ago),
+        }
+    }
+}
+
+#[cfg(test)]
+mod motion {
+    use super::*;
+    use crate::inputs::{GamepadInput, KeyboardInput};
+
+    #[derive(Clone, Copy, Debug, PartialEq, Eq)]
+    enum Command {
+        Fireball,
+        Uppercut,
+        Dash,
+    }
+
+    /// direction and key pressed of each frame, oldest first
+    #[derive(Default)]
+    struct Frames(Vec<(Direction, Option<char>)>);
+
+    impl MotionSource<char> for Frames {
+        fn direction(&self, frames_ago: usize) -> Direction {
+            self.0
+                .iter()
+                .rev()
+                .nth(frames_ago)
+                .map_or(Direction::Neutral, |f| f.0)
+        }
+
+        fn is_pressed(&self, button: char, frames_ago: usize) -> bool {
+            self.0
+                .iter()
+                .rev()
+                .nth(frames_ago)
+                .map_or(false, |f| f.1 == Some(button))
+        }
+
+        fn history_length(&self) -> usize {
+            self.0.len()
+        }
+    }
+
+    fn motion(command: Command, notation: &str, button: Option<char>) -> Motion<Command, char> {
+        let mut steps = MotionStep::directions(notation).unwrap();
+        steps.extend(button.map(MotionStep::Press));
+        Motion::new(command, steps)
+    }
+
+    /// feeds numpad digits to a new recognizer, with P for punch on the frame of the digit before
+    fn play(
+        motions: Vec<Motion<Command, char>>,
+        facing: Facing,
+        frames: &str,
+    ) -> Vec<MotionMatch<Command>> {
+        let mut recognizer = MotionRecognizer::new(motions);
+        recognizer.set_facing(facing);
+        let mut matches = Vec::new();
+        let mut history = Frames::default();
+        let mut chars = frames.chars().peekable();
+        while let Some(c) = chars.next() {
+            let digit = c.to_digit(10).unwrap() as u8;
+            let button = chars.next_if_eq(&'P');
+            history
+                .0
+                .push((Direction::from_numpad(digit).unwrap(), button));
+            matches.extend(recognizer.update(&history));
+        }
+        matches
+    }
+
+    fn fireball(frames: &str) -> Vec<MotionMatch<Command>> {
+        let motions = vec![motion(Command::Fireball, "236", Some('P'))];
+        play(motions, Facing::Right, frames)
+    }
+
+    #[test]
+    fn from_components() {
+        assert_eq!(Direction::from_components(0, 0), Direction::Neutral);
+        assert_eq!(Direction::from_components(1, 1), Direction::DownForward);
+        assert_eq!(Direction::from_components(-1, -1), Direction::UpBack);
+        assert_eq!(Direction::from_components(0, -1), Direction::Up);
+    }
+
+    #[test]
+    fn directions() {
+        assert_eq!(
+            MotionStep::<char>::directions("236"),
+            Some(vec![
+                MotionStep::Direction(Direction::Down),
+                MotionStep::Direction(Direction::DownForward),
+                MotionStep::Direction(Direction::Forward),
+            ])
+        );
+        assert_eq!(MotionStep::<char>::directions("20"), None);
+    }
+
+    #[test]
+    fn quarter_circle() {
+        assert_eq!(
+            fireball("55236P55"),
+            vec![MotionMatch {
+                command: Command::Fireball,
+                frame: 4
+            }]
+        );
+        assert_eq!(fireball("5P"), vec![], "button alone");
+        assert_eq!(fireball("5226P"), vec![], "missing a step");
+        assert_eq!(fireball("5632P"), vec![], "steps out of order");
+    }
+
+    #[test]
+    fn leniency() {
+        assert_eq!(fireball("5222233666P").len(), 1, "holding a step is fine");
+        assert_eq!(
+            fireball("5212366P").len(),
+            1,
+            "sloppy inputs between steps are fine"
+        );
+        assert_eq!(
+            fireball("52555555555536P").len(),
+            0,
+            "too long between steps"
+        );
+
+        let motions = vec![motion(Command::Fireball, "236", Some('P')).with_window(20)];
+        assert_eq!(
+            play(motions, Facing::Right, "52555555555536P").len(),
+            1,
+            "longer window"
+        );
+    }
+
+    #[test]
+    fn priority() {
+        // a dragon punch ends in a quarter circle, so it needs to win
+        let motions = vec![
+            motion(Command::Fireball, "236", Some('P')),
+            motion(Command::Uppercut, "623", Some('P')).with_priority(1),
+        ];
+        let matches = play(motions.clone(), Facing::Right, "56236P");
+        assert_eq!(matches.len(), 1);
+        assert_eq!(matches[0].command, Command::Uppercut);
+
+        let matches = play(motions, Facing::Right, "5236P");
+        assert_eq!(matches[0].command, Command::Fireball);
+    }
+
+    #[test]
+    fn direction_only() {
+        let matches = play(
+            vec![motion(Command::Dash, "656", None)],
+            Facing::Right,
+            "56566666",
+        );
+        assert_eq!(matches.len(), 1, "fires once while held");
+        assert_eq!(matches[0].frame, 3);
+    }
+
+    #[test]
+    fn facing() {
+        let motions = vec![motion(Command::Fireball, "236", Some('P'))];
+        assert_eq!(play(motions.clone(), Facing::Left, "5236P").len(), 0);
+        assert_eq!(
+            play(motions, Facing::Left, "5214P").len(),
+            1,
+            "forward is left when facing left"
+        );
+    }
+
+    #[test]
+    fn keyboard() {
+        let mut steps = MotionStep::directions("236").unwrap();
+        steps.push(MotionStep::Press(Key::KeyJ));
+        let mut recognizer = MotionRecognizer::new(vec![Motion::new(Command::Fireball, steps)]);
+        let mut snapshot = KeyboardSnapshot::new();
+
+        let mut fired = None;
+        for keys in [
+            vec![Key::ArrowDown],
+            vec![Key::ArrowDown, Key::ArrowRight],
+            vec![Key::ArrowRight, Key::KeyJ],
+        ] {
+            snapshot.add_input(KeyboardInput::from(keys));
+            let source = KeyboardMotionSource {
+                snapshot: &snapshot,
+                keys: DirectionKeys::ARROWS,
+            };
+            fired = recognizer.update(&source).or(fired);
+        }
+        assert_eq!(fired.map(|m| m.command), Some(Command::Fireball));
+    }
+
+    /// quarter circle and punch with 7 frames of neutral after each step, 25 frames in all
+    fn slow_fireball<const N: usize>(snapshot: &mut KeyboardSnapshot<N>) -> Option<Command> {
+        let mut steps = MotionStep::directions("236").unwrap();
+        steps.push(MotionStep::Press(Key::KeyJ));
+        let mut recognizer = MotionRecognizer::new(vec![Motion::new(Command::Fireball, steps)]);
+
+        let mut fired = None;
+        for keys in [
+            vec![Key::ArrowDown],
+            vec![Key::ArrowDown, Key::ArrowRight],
+            vec![Key::ArrowRight],
+            vec![Key::KeyJ],
+        ] {
+            snapshot.add_input(KeyboardInput::from(keys));
+            for _ in 0..7 {
+                let source = KeyboardMotionSource {
+                    snapshot: &*snapshot,
+                    keys: DirectionKeys::ARROWS,
+                };
+                fired = recognizer.update(&source).or(fired);
+                snapshot.add_input(KeyboardInput::new());
+            }
+        }
+        fired.map(|m| m.command)
+    }
+
+    #[test]
+    fn history_length() {
+        assert_eq!(
+            slow_fireball(&mut KeyboardSnapshot::<33>::default()),
+            Some(Command::Fireball)
+        );
+        assert_eq!(
+            slow_fireball(&mut KeyboardSnapshot::new()),
+            None,
+            "steps older than the snapshot are missed"
+        );
+    }
+
+    #[test]
+    fn gamepad() {
+        let mut steps = MotionStep::directions("236").unwrap();
+        steps.push(MotionStep::Press(ButtonPosition::RightFaceLeft));
+        let mut recognizer = MotionRecognizer::new(vec![Motion::new(Command::Fireball, steps)]);
+        let mut snapshot = GamepadSnapshot::new();
+
+        let mut fired = None;
+        for (x, y, punch) in [(0, 10000, false), (8000, 8000, false), (10000, 0, true)] {
+            let mut input = GamepadInput::new();
+            input.set_axes(Hand::Left, x, y);
+            if punch {
+                input.set_button(ButtonPosition::RightFaceLeft, 10000);
+            }
+            snapshot.add_input(input);
+            fired = recognizer
+                .update(&GamepadMotionSource::new(&snapshot))
+                .or(fired);
+        }
+        assert_eq!(fired.map(|m| m.command), Some(Command::Fireball));
+    }
+}
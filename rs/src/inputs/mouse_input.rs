@@ -0,0 +1,113 @@
+use super::binary_reader::BinaryReader;
+use super::binary_writer::BinaryWriter;
+use super::InputType;
+use crate::PogpError;
+
+pub const MOUSE_LENGTH: usize = 1 + 4 + 4 + 1; // type, x, y, bool for down
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct MouseInput {
+    /// x coordinate in px
+    pub x: u32,
+    /// y coordinate in px
+    pub y: u32,
+    /// is mouse being clicked this frame
+    pub is_down: bool,
+}
+
+impl MouseInput {
+    pub fn new(x: u32, y: u32, is_down: bool) -> Self {
+        Self { x, y, is_down }
+    }
+
+    /// writes this input in the pogp binary format to the start of `buffer`
+    pub fn write_to(&self, buffer: &mut [u8]) {
+        if buffer.len() < MOUSE_LENGTH {
+            panic!(
+                "Expected buffer of at least {} bytes, but it was {}",
+                MOUSE_LENGTH,
+                buffer.len()
+            );
+        }
+        let mut writer = BinaryWriter::new(buffer);
+        writer.write_u8(InputType::Mouse as u8);
+        writer.write_u32(self.x);
+        writer.write_u32(self.y);
+        writer.write_bool(self.is_down);
+    }
+
+    pub fn to_bytes(&self) -> [u8; MOUSE_LENGTH] {
+        let mut buffer = [0; MOUSE_LENGTH];
+        self.write_to(&mut buffer);
+        buffer
+    }
+
+    /// decodes `buffer` like `MouseInput::try_from`, but panics if it isn't a valid mouse input.
+    pub fn from_bytes_unchecked(buffer: &[u8]) -> Self {
+        MouseInput::try_from(buffer).unwrap_or_else(|e| panic!("{}", e))
+    }
+}
+
+impl From<[u8; MOUSE_LENGTH]> for MouseInput {
+    /// panics if `buffer` is not a mouse input, see `MouseInput::try_from` for a fallible version
+    fn from(buffer: [u8; MOUSE_LENGTH]) -> Self {
+        MouseInput::try_from(&buffer as &[u8]).unwrap_or_else(|e| panic!("{}", e))
+    }
+}
+
+impl TryFrom<&[u8]> for MouseInput {
+    type Error = PogpError;
+
+    fn try_from(buffer: &[u8]) -> Result<Self, Self::Error> {
+        let mut reader = BinaryReader::new(buffer);
+        reader.read_input_type(InputType::Mouse)?;
+        let x = reader.read_u32()?;
+        let y = reader.read_u32()?;
+        let is_down = reader.read_bool()?;
+        Ok(Self { x, y, is_down })
+    }
+}
+
+#[cfg(test)]
+mod mouse_input {
+    use super::*;
+
+    #[test]
+    fn from_u8array() {
+        // matches the typescript MarshalInput.encodeMouse output
+        let buffer: [u8; 10] = [0x03, 0x64, 0x00, 0x00, 0x00, 0x38, 0x04, 0x00, 0x00, 0x01];
+        let input = MouseInput::from(buffer);
+        assert_eq!(input.x, 100);
+        assert_eq!(input.y, 1080);
+        assert_eq!(input.is_down, true);
+    }
+
+    #[test]
+    fn round_trip() {
+        let input = MouseInput::new(1920, 7, false);
+        assert_eq!(MouseInput::from(input.to_bytes()), input);
+
+        let input = MouseInput::new(0, u32::MAX, true);
+        assert_eq!(MouseInput::from(input.to_bytes()), input);
+    }
+
+    #[test]
+    #[should_panic(expected = "first byte to be 3, but it was 4")]
+    fn from_u8array_bad_input_type() {
+        let mut buffer: [u8; 10] = [0; 10];
+        buffer[0] = InputType::Keyboard as u8;
+        let _nope = MouseInput::from(buffer);
+    }
+
+    #[test]
+    fn try_from_truncated() {
+        let buffer: [u8; 9] = [InputType::Mouse as u8, 0, 0, 0, 0, 0, 0, 0, 0];
+        assert_eq!(
+            MouseInput::try_from(&buffer as &[u8]),
+            Err(PogpError::Truncated {
+                expected: 10,
+                actual: 9
+            })
+        );
+    }
+}
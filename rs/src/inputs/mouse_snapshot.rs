@@ -0,0 +1,148 @@
+use super::MouseInput;
+
+const INPUT_BUFFER_LENGTH: usize = 10;
+
+#[derive(Debug)]
+pub struct MouseSnapshot {
+    inputs: [MouseInput; INPUT_BUFFER_LENGTH],
+    index: usize,
+    // number of inputs received, capped at INPUT_BUFFER_LENGTH
+    len: usize,
+}
+
+impl MouseSnapshot {
+    pub fn new() -> Self {
+        Self {
+            inputs: Default::default(),
+            index: 0,
+            len: 0,
+        }
+    }
+
+    pub fn add_input(&mut self, input: MouseInput) {
+        self.inputs[self.index] = input;
+        self.advance_index();
+        self.len = (self.len + 1).min(INPUT_BUFFER_LENGTH);
+    }
+
+    /// true only for the frame that the mouse is first clicked
+    pub fn is_click_down(&self) -> bool {
+        self.inputs[self.head()].is_down && !self.inputs[self.last_frame()].is_down
+    }
+
+    /// true for every frame the mouse is held down
+    pub fn is_clicked(&self) -> bool {
+        self.inputs[self.head()].is_down
+    }
+
+    /// true only for the frame that the click is released
+    pub fn is_click_up(&self) -> bool {
+        !self.inputs[self.head()].is_down && self.inputs[self.last_frame()].is_down
+    }
+
+    pub fn position(&self) -> (u32, u32) {
+        let head = &self.inputs[self.head()];
+        (head.x, head.y)
+    }
+
+    /// movement in px since the last frame, zero until two frames have been added
+    pub fn delta(&self) -> (i64, i64) {
+        if self.len < 2 {
+            return (0, 0);
+        }
+        let head = &self.inputs[self.head()];
+        let last = &self.inputs[self.last_frame()];
+        (head.x as i64 - last.x as i64, head.y as i64 - last.y as i64)
+    }
+
+    fn advance_index(&mut self) {
+        self.index = if self.index == INPUT_BUFFER_LENGTH - 1 {
+            0
+        } else {
+            self.index + 1
+        }
+    }
+
+    fn last_frame(&self) -> usize {
+        match self.index {
+            0 => INPUT_BUFFER_LENGTH - 2,
+            1 => INPUT_BUFFER_LENGTH - 1,
+            _ => self.index - 2,
+        }
+    }
+
+    fn head(&self) -> usize {
+        if self.index == 0 {
+            INPUT_BUFFER_LENGTH - 1
+        } else {
+            self.index - 1
+        }
+    }
+}
+
+impl Default for MouseSnapshot {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod mouse_snapshot {
+    use super::*;
+
+    #[test]
+    fn add_input() {
+        let mut snapshot = MouseSnapshot::new();
+
+        for x in 0..40 {
+            snapshot.add_input(MouseInput::new(x, 0, false));
+        }
+        assert_eq!(snapshot.position(), (39, 0));
+        assert_eq!(snapshot.delta(), (1, 0));
+    }
+
+    #[test]
+    fn is_click_down() {
+        let mut snapshot = MouseSnapshot::new();
+        assert_eq!(snapshot.is_click_down(), false, "false on empty");
+
+        snapshot.add_input(MouseInput::new(0, 0, true));
+        assert_eq!(snapshot.is_click_down(), true, "true on click down");
+
+        snapshot.add_input(MouseInput::new(0, 0, true));
+        assert_eq!(snapshot.is_click_down(), false, "false while clicked");
+        assert_eq!(snapshot.is_clicked(), true, "clicked while held");
+
+        snapshot.add_input(Default::default());
+        assert_eq!(snapshot.is_click_down(), false, "false on click up");
+    }
+
+    #[test]
+    fn is_click_up() {
+        let mut snapshot = MouseSnapshot::new();
+        assert_eq!(snapshot.is_click_up(), false, "false on empty");
+
+        snapshot.add_input(MouseInput::new(0, 0, true));
+        assert_eq!(snapshot.is_click_up(), false, "false on click down");
+
+        snapshot.add_input(Default::default());
+        assert_eq!(snapshot.is_click_up(), true, "true on click up");
+
+        snapshot.add_input(Default::default());
+        assert_eq!(snapshot.is_click_up(), false, "false on next frame");
+    }
+
+    #[test]
+    fn delta() {
+        let mut snapshot = MouseSnapshot::new();
+        snapshot.add_input(MouseInput::new(100, 100, false));
+        assert_eq!(snapshot.delta(), (0, 0), "zero on first frame");
+
+        snapshot.add_input(MouseInput::new(90, 120, false));
+        assert_eq!(snapshot.position(), (90, 120));
+        assert_eq!(snapshot.delta(), (-10, 20));
+
+        snapshot.add_input(MouseInput::new(90, 120, true));
+        assert_eq!(snapshot.delta(), (0, 0), "zero when still");
+    }
+}
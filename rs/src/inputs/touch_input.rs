@@ -0,0 +1,224 @@
+use super::binary_reader::BinaryReader;
+use super::binary_writer::BinaryWriter;
+use super::InputType;
+use crate::PogpError;
+
+const HEADER_LENGTH: usize = 1 + 4 + 4 + 2; // type, width, height, fingers.length
+const FINGER_LENGTH: usize = 4 + 4 + 4; // x, y, pressure
+
+/// most fingers a touch input carries, so inputs hold their fingers inline and a peer can't make
+/// decoding or tracking allocate
+pub const MAX_FINGERS: usize = 10;
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Finger {
+    /// x coordinate in px
+    pub x: u32,
+    /// y coordinate in px
+    pub y: u32,
+    /// percentage of pressure with four digits of precision, e.g. 10000 is 100%
+    pub pressure: u32,
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct TouchInput {
+    /// width of the touch surface in px
+    pub width: u32,
+    /// height of the touch surface in px
+    pub height: u32,
+    /// the first `fingers_length` are in use, the rest stay default so equality holds
+    fingers: [Finger; MAX_FINGERS],
+    fingers_length: usize,
+}
+
+impl TouchInput {
+    pub fn new(width: u32, height: u32) -> Self {
+        Self {
+            width,
+            height,
+            fingers: [Finger::default(); MAX_FINGERS],
+            fingers_length: 0,
+        }
+    }
+
+    pub fn fingers(&self) -> &[Finger] {
+        &self.fingers[..self.fingers_length]
+    }
+
+    /// errors with `PogpError::TooMany` if the input already has `MAX_FINGERS` fingers
+    pub fn add_finger(&mut self, x: u32, y: u32, pressure: u32) -> Result<(), PogpError> {
+        if self.fingers_length >= MAX_FINGERS {
+            return Err(too_many_fingers(self.fingers_length + 1));
+        }
+        self.fingers[self.fingers_length] = Finger { x, y, pressure };
+        self.fingers_length += 1;
+        Ok(())
+    }
+
+    /// number of bytes this input occupies in the pogp binary format
+    pub fn byte_length(&self) -> usize {
+        HEADER_LENGTH + FINGER_LENGTH * self.fingers_length
+    }
+
+    /// writes this input in the pogp binary format to the start of `buffer`
+    pub fn write_to(&self, buffer: &mut [u8]) {
+        if buffer.len() < self.byte_length() {
+            panic!(
+                "Expected buffer of at least {} bytes, but it was {}",
+                self.byte_length(),
+                buffer.len()
+            );
+        }
+        let mut writer = BinaryWriter::new(buffer);
+        writer.write_u8(InputType::Touch as u8);
+        writer.write_u32(self.width);
+        writer.write_u32(self.height);
+        writer.write_u16(self.fingers_length as u16);
+
+        for finger in self.fingers() {
+            writer.write_u32(finger.x);
+            writer.write_u32(finger.y);
+            writer.write_u32(finger.pressure);
+        }
+    }
+
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut buffer = vec![0; self.byte_length()];
+        self.write_to(&mut buffer);
+        buffer
+    }
+
+    /// decodes `buffer` like `TouchInput::try_from`, but panics if it isn't a valid touch input.
+    pub fn from_bytes_unchecked(buffer: &[u8]) -> Self {
+        TouchInput::try_from(buffer).unwrap_or_else(|e| panic!("{}", e))
+    }
+}
+
+impl TryFrom<&[u8]> for TouchInput {
+    type Error = PogpError;
+
+    fn try_from(buffer: &[u8]) -> Result<Self, Self::Error> {
+        let mut reader = BinaryReader::new(buffer);
+        reader.read_input_type(InputType::Touch)?;
+        let width = reader.read_u32()?;
+        let height = reader.read_u32()?;
+        let fingers_length = reader.read_u16()? as usize;
+        if fingers_length > MAX_FINGERS {
+            return Err(too_many_fingers(fingers_length));
+        }
+
+        // check the whole input fits before trusting the length
+        let byte_length = HEADER_LENGTH + FINGER_LENGTH * fingers_length;
+        if buffer.len() < byte_length {
+            return Err(PogpError::Truncated {
+                expected: byte_length,
+                actual: buffer.len(),
+            });
+        }
+
+        let mut input = Self::new(width, height);
+        for finger in &mut input.fingers[..fingers_length] {
+            finger.x = reader.read_u32()?;
+            finger.y = reader.read_u32()?;
+            finger.pressure = reader.read_u32()?;
+        }
+        input.fingers_length = fingers_length;
+        Ok(input)
+    }
+}
+
+fn too_many_fingers(actual: usize) -> PogpError {
+    PogpError::TooMany {
+        what: "fingers",
+        max: MAX_FINGERS,
+        actual,
+    }
+}
+
+#[cfg(test)]
+mod touch_input {
+    use super::*;
+
+    #[test]
+    fn from_slice_ref() {
+        let buffer: &[u8] = &[
+            0x02, 0x80, 0x07, 0x00, 0x00, 0x38, 0x04, 0x00, 0x00, 0x01, 0x00, 0x64, 0x00, 0x00,
+            0x00, 0xc8, 0x00, 0x00, 0x00, 0x10, 0x27, 0x00, 0x00,
+        ];
+        let input = TouchInput::try_from(buffer).unwrap();
+        assert_eq!(input.width, 1920);
+        assert_eq!(input.height, 1080);
+        assert_eq!(
+            input.fingers(),
+            &[Finger {
+                x: 100,
+                y: 200,
+                pressure: 10000
+            }]
+        );
+    }
+
+    #[test]
+    fn round_trip() {
+        let mut input = TouchInput::new(1170, 2532);
+        input.add_finger(0, 0, 0).unwrap();
+        input.add_finger(1169, 2531, 5000).unwrap();
+
+        let bytes = input.to_bytes();
+        assert_eq!(bytes.len(), 11 + 12 * 2);
+        assert_eq!(TouchInput::try_from(&bytes as &[u8]).unwrap(), input);
+    }
+
+    #[test]
+    fn try_from_bad_input_type() {
+        let buffer: [u8; 11] = [InputType::Mouse as u8, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0];
+        assert_eq!(
+            TouchInput::try_from(&buffer as &[u8]),
+            Err(PogpError::WrongInputType {
+                expected: InputType::Touch,
+                actual: 3
+            })
+        );
+    }
+
+    #[test]
+    fn try_from_truncated() {
+        // claims two fingers but only has one
+        let mut input = TouchInput::new(10, 10);
+        input.add_finger(1, 1, 1).unwrap();
+        let mut bytes = input.to_bytes();
+        bytes[9] = 2;
+        assert_eq!(
+            TouchInput::try_from(&bytes as &[u8]),
+            Err(PogpError::Truncated {
+                expected: 35,
+                actual: 23
+            })
+        );
+    }
+
+    #[test]
+    fn too_many_fingers() {
+        let mut input = TouchInput::new(10, 10);
+        for i in 0..MAX_FINGERS as u32 {
+            input.add_finger(i, i, 1).unwrap();
+        }
+        assert_eq!(
+            input.add_finger(0, 0, 1),
+            Err(PogpError::TooMany {
+                what: "fingers",
+                max: 10,
+                actual: 11
+            })
+        );
+
+        // a peer claiming 65535 fingers is rejected before anything is allocated
+        let mut bytes = input.to_bytes();
+        bytes[9] = 0xff;
+        bytes[10] = 0xff;
+        assert_eq!(
+            TouchInput::try_from(&bytes as &[u8]).map_err(|e| e.to_string()),
+            Err("Expected at most 10 fingers, but there were 65535".to_string())
+        );
+    }
+}
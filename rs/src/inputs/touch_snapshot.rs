@@ -0,0 +1,256 @@
+use super::{TouchInput, MAX_FINGERS};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TouchPhase {
+    /// the finger touched down this frame
+    Began,
+    /// the finger is still down and changed position this frame
+    Moved,
+    /// the finger is still down and did not change position this frame
+    Stationary,
+    /// the finger was lifted this frame, its position is where it was last seen
+    Ended,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Touch {
+    /// stable identifier for the finger from the frame it begins to the frame it ends
+    pub id: u32,
+    pub x: u32,
+    pub y: u32,
+    pub pressure: u32,
+    pub phase: TouchPhase,
+}
+
+/// distance a finger can move in one frame and still be the same touch, a fast swipe on a
+/// phone covers about 100px a frame at 60fps
+pub const DEFAULT_MAX_TOUCH_DISTANCE: u32 = 200;
+
+/// TouchSnapshot follows fingers across frames.
+///
+/// The touch binary format does not identify fingers, so each frame the fingers are matched to
+/// the touches of the previous frame by shortest distance. Fingers that can't be matched begin a
+/// new touch, and touches that can't be matched end.
+#[derive(Debug)]
+pub struct TouchSnapshot {
+    touches: Vec<Touch>,
+    next_id: u32,
+    max_distance: u32,
+    // scratch space for matching, kept so tracking doesn't allocate every frame
+    pairs: Vec<(u64, usize, usize)>,
+    touch_matches: Vec<Option<usize>>,
+    finger_matched: Vec<bool>,
+}
+
+impl TouchSnapshot {
+    pub fn new() -> Self {
+        Self::with_max_distance(DEFAULT_MAX_TOUCH_DISTANCE)
+    }
+
+    /// fingers that move further than `max_distance` px in one frame are treated as a new touch
+    pub fn with_max_distance(max_distance: u32) -> Self {
+        // touches from last frame can be ending while as many new ones begin
+        let max_touches = 2 * MAX_FINGERS;
+        Self {
+            touches: Vec::with_capacity(max_touches),
+            next_id: 0,
+            max_distance,
+            pairs: Vec::with_capacity(max_touches * MAX_FINGERS),
+            touch_matches: Vec::with_capacity(max_touches),
+            finger_matched: Vec::with_capacity(MAX_FINGERS),
+        }
+    }
+
+    pub fn add_input(&mut self, input: &TouchInput) {
+        // touches that ended last frame are gone
+        self.touches.retain(|t| t.phase != TouchPhase::Ended);
+
+        let fingers = input.fingers();
+        let max_distance = self.max_distance as u64 * self.max_distance as u64;
+        self.pairs.clear();
+        for (touch_index, touch) in self.touches.iter().enumerate() {
+            for (finger_index, finger) in fingers.iter().enumerate() {
+                let distance = distance_squared((touch.x, touch.y), (finger.x, finger.y));
+                if distance <= max_distance {
+                    self.pairs.push((distance, touch_index, finger_index));
+                }
+            }
+        }
+        // closest pairs are matched first, ties are broken by order for determinism
+        self.pairs.sort_unstable();
+
+        self.touch_matches.clear();
+        self.touch_matches.resize(self.touches.len(), None);
+        self.finger_matched.clear();
+        self.finger_matched.resize(fingers.len(), false);
+        for &(_, touch_index, finger_index) in &self.pairs {
+            if self.touch_matches[touch_index].is_some() || self.finger_matched[finger_index] {
+                continue;
+            }
+            self.touch_matches[touch_index] = Some(finger_index);
+            self.finger_matched[finger_index] = true;
+        }
+
+        for (touch, matched) in self.touches.iter_mut().zip(&self.touch_matches) {
+            match matched {
+                Some(finger_index) => {
+                    let finger = fingers[*finger_index];
+                    touch.phase = if (finger.x, finger.y) == (touch.x, touch.y) {
+                        TouchPhase::Stationary
+                    } else {
+                        TouchPhase::Moved
+                    };
+                    touch.x = finger.x;
+                    touch.y = finger.y;
+                    touch.pressure = finger.pressure;
+                }
+                None => touch.phase = TouchPhase::Ended,
+            }
+        }
+
+        for (finger, _) in fingers
+            .iter()
+            .zip(&self.finger_matched)
+            .filter(|(_, matched)| !**matched)
+        {
+            self.touches.push(Touch {
+                id: self.next_id,
+                x: finger.x,
+                y: finger.y,
+                pressure: finger.pressure,
+                phase: TouchPhase::Began,
+            });
+            self.next_id = self.next_id.wrapping_add(1);
+        }
+    }
+
+    /// touches that are down this frame, plus touches that ended this frame
+    pub fn touches(&self) -> &[Touch] {
+        &self.touches
+    }
+
+    pub fn touch(&self, id: u32) -> Option<&Touch> {
+        self.touches.iter().find(|t| t.id == id)
+    }
+
+    pub fn began(&self) -> impl Iterator<Item = &Touch> {
+        self.with_phase(TouchPhase::Began)
+    }
+
+    pub fn ended(&self) -> impl Iterator<Item = &Touch> {
+        self.with_phase(TouchPhase::Ended)
+    }
+
+    fn with_phase(&self, phase: TouchPhase) -> impl Iterator<Item = &Touch> {
+        self.touches.iter().filter(move |t| t.phase == phase)
+    }
+}
+
+impl Default for TouchSnapshot {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn distance_squared(a: (u32, u32), b: (u32, u32)) -> u64 {
+    let dx = a.0.abs_diff(b.0) as u64;
+    let dy = a.1.abs_diff(b.1) as u64;
+    dx * dx + dy * dy
+}
+
+#[cfg(test)]
+mod touch_snapshot {
+    use super::*;
+
+    fn touch_input(fingers: &[(u32, u32)]) -> TouchInput {
+        let mut input = TouchInput::new(1000, 1000);
+        for (x, y) in fingers {
+            input.add_finger(*x, *y, 10000).unwrap();
+        }
+        input
+    }
+
+    #[test]
+    fn lifecycle() {
+        let mut snapshot = TouchSnapshot::new();
+        assert_eq!(snapshot.touches().len(), 0, "empty on start");
+
+        snapshot.add_input(&touch_input(&[(10, 10)]));
+        assert_eq!(snapshot.touches().len(), 1);
+        assert_eq!(snapshot.touches()[0].phase, TouchPhase::Began);
+        let id = snapshot.touches()[0].id;
+
+        snapshot.add_input(&touch_input(&[(10, 10)]));
+        assert_eq!(snapshot.touch(id).unwrap().phase, TouchPhase::Stationary);
+
+        snapshot.add_input(&touch_input(&[(15, 12)]));
+        let touch = snapshot.touch(id).unwrap();
+        assert_eq!(touch.phase, TouchPhase::Moved);
+        assert_eq!((touch.x, touch.y), (15, 12));
+
+        snapshot.add_input(&touch_input(&[]));
+        let touch = snapshot.touch(id).unwrap();
+        assert_eq!(touch.phase, TouchPhase::Ended);
+        assert_eq!((touch.x, touch.y), (15, 12), "ends where last seen");
+
+        snapshot.add_input(&touch_input(&[]));
+        assert_eq!(snapshot.touch(id), None, "removed after ending");
+    }
+
+    #[test]
+    fn stable_ids() {
+        let mut snapshot = TouchSnapshot::new();
+        snapshot.add_input(&touch_input(&[(100, 100), (900, 900)]));
+        let left = snapshot.touches()[0].id;
+        let right = snapshot.touches()[1].id;
+        assert_ne!(left, right);
+
+        // order of fingers in the input changes, identities do not
+        snapshot.add_input(&touch_input(&[(880, 910), (110, 90)]));
+        let position = |id| {
+            let touch = snapshot.touch(id).unwrap();
+            (touch.x, touch.y)
+        };
+        assert_eq!(position(left), (110, 90));
+        assert_eq!(position(right), (880, 910));
+    }
+
+    #[test]
+    fn began_and_ended() {
+        let mut snapshot = TouchSnapshot::new();
+        snapshot.add_input(&touch_input(&[(100, 100)]));
+        let first = snapshot.touches()[0].id;
+
+        snapshot.add_input(&touch_input(&[(500, 500), (101, 100)]));
+        assert_eq!(snapshot.began().count(), 1);
+        assert_ne!(snapshot.began().next().unwrap().id, first);
+        assert_eq!(snapshot.ended().count(), 0);
+
+        snapshot.add_input(&touch_input(&[(500, 500)]));
+        assert_eq!(snapshot.began().count(), 0);
+        assert_eq!(snapshot.ended().next().unwrap().id, first);
+    }
+
+    #[test]
+    fn max_distance() {
+        let mut snapshot = TouchSnapshot::with_max_distance(50);
+        snapshot.add_input(&touch_input(&[(100, 100)]));
+        let first = snapshot.touches()[0].id;
+
+        snapshot.add_input(&touch_input(&[(800, 800)]));
+        assert_eq!(snapshot.touch(first).unwrap().phase, TouchPhase::Ended);
+        assert_eq!(snapshot.began().count(), 1, "far finger is a new touch");
+    }
+
+    #[test]
+    fn lift_and_tap_elsewhere() {
+        let mut snapshot = TouchSnapshot::new();
+        snapshot.add_input(&touch_input(&[(50, 50)]));
+        let first = snapshot.touches()[0].id;
+
+        // one finger lifts as another taps across the screen on the same frame
+        snapshot.add_input(&touch_input(&[(900, 900)]));
+        assert_eq!(snapshot.touch(first).unwrap().phase, TouchPhase::Ended);
+        assert_eq!(snapshot.began().count(), 1);
+    }
+}
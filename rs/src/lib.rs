@@ -1 +1,7 @@
+// tests assert on bools explicitly and live in a module named after their file
+#![cfg_attr(test, allow(clippy::bool_assert_comparison, clippy::module_inception))]
+
 pub mod inputs;
+
+mod error;
+pub use error::PogpError;
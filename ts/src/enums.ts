@@ -91,4 +91,69 @@ export enum Key {
 	Period,
 	Slash,
 	IntlRo,
+
+	Escape,
+	Delete,
+	Insert,
+	Home,
+	End,
+	PageUp,
+	PageDown,
+
+	F1,
+	F2,
+	F3,
+	F4,
+	F5,
+	F6,
+	F7,
+	F8,
+	F9,
+	F10,
+	F11,
+	F12,
+	F13,
+	F14,
+	F15,
+
+	Lang1,
+	Lang2,
+	Lang3,
+	Lang4,
+	Lang5,
+	Convert,
+	NonConvert,
+	KanaMode,
+	NumpadComma,
+
+	NumLock,
+	Numpad0,
+	Numpad1,
+	Numpad2,
+	Numpad3,
+	Numpad4,
+	Numpad5,
+	Numpad6,
+	Numpad7,
+	Numpad8,
+	Numpad9,
+	NumpadAdd,
+	NumpadSubtract,
+	NumpadMultiply,
+	NumpadDivide,
+	NumpadDecimal,
+	NumpadEnter,
+	NumpadEqual,
+
+	PrintScreen,
+	ScrollLock,
+	Pause,
+
+	MediaPlayPause,
+	MediaStop,
+	MediaTrackNext,
+	MediaTrackPrevious,
+	AudioVolumeUp,
+	AudioVolumeDown,
+	AudioVolumeMute,
 }
\ No newline at end of file
@@ -114,6 +114,65 @@ namespace Pogp {
 				case 66: return KeyCode.Period;
 				case 67: return KeyCode.Slash;
 				case 68: return KeyCode.None; // IntlRo
+				case 69: return KeyCode.Escape;
+				case 70: return KeyCode.Delete;
+				case 71: return KeyCode.Insert;
+				case 72: return KeyCode.Home;
+				case 73: return KeyCode.End;
+				case 74: return KeyCode.PageUp;
+				case 75: return KeyCode.PageDown;
+				case 76: return KeyCode.F1;
+				case 77: return KeyCode.F2;
+				case 78: return KeyCode.F3;
+				case 79: return KeyCode.F4;
+				case 80: return KeyCode.F5;
+				case 81: return KeyCode.F6;
+				case 82: return KeyCode.F7;
+				case 83: return KeyCode.F8;
+				case 84: return KeyCode.F9;
+				case 85: return KeyCode.F10;
+				case 86: return KeyCode.F11;
+				case 87: return KeyCode.F12;
+				case 88: return KeyCode.F13;
+				case 89: return KeyCode.F14;
+				case 90: return KeyCode.F15;
+				case 91: return KeyCode.None; // Lang1
+				case 92: return KeyCode.None; // Lang2
+				case 93: return KeyCode.None; // Lang3
+				case 94: return KeyCode.None; // Lang4
+				case 95: return KeyCode.None; // Lang5
+				case 96: return KeyCode.None; // Convert
+				case 97: return KeyCode.None; // NonConvert
+				case 98: return KeyCode.None; // KanaMode
+				case 99: return KeyCode.None; // NumpadComma
+				case 100: return KeyCode.Numlock;
+				case 101: return KeyCode.Keypad0;
+				case 102: return KeyCode.Keypad1;
+				case 103: return KeyCode.Keypad2;
+				case 104: return KeyCode.Keypad3;
+				case 105: return KeyCode.Keypad4;
+				case 106: return KeyCode.Keypad5;
+				case 107: return KeyCode.Keypad6;
+				case 108: return KeyCode.Keypad7;
+				case 109: return KeyCode.Keypad8;
+				case 110: return KeyCode.Keypad9;
+				case 111: return KeyCode.KeypadPlus;
+				case 112: return KeyCode.KeypadMinus;
+				case 113: return KeyCode.KeypadMultiply;
+				case 114: return KeyCode.KeypadDivide;
+				case 115: return KeyCode.KeypadPeriod;
+				case 116: return KeyCode.KeypadEnter;
+				case 117: return KeyCode.KeypadEquals;
+				case 118: return KeyCode.Print;
+				case 119: return KeyCode.ScrollLock;
+				case 120: return KeyCode.Pause;
+				case 121: return KeyCode.None; // MediaPlayPause
+				case 122: return KeyCode.None; // MediaStop
+				case 123: return KeyCode.None; // MediaTrackNext
+				case 124: return KeyCode.None; // MediaTrackPrevious
+				case 125: return KeyCode.None; // AudioVolumeUp
+				case 126: return KeyCode.None; // AudioVolumeDown
+				case 127: return KeyCode.None; // AudioVolumeMute
 
 				default: return KeyCode.None;
 			}